@@ -1,5 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::solana_program::sysvar::slot_hashes;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 declare_id!("8g9EesTi6VgFFPS41musSS31t8ihU9wpRcFrqRnqcXZd");
 
@@ -13,23 +21,54 @@ pub mod crash_game {
     pub fn initialize(
         ctx: Context<Initialize>,
         admin: Pubkey,
-        treasury: Pubkey,
         tax_bps: u16,
+        house_edge_bps: u16,
     ) -> Result<()> {
         require!(tax_bps <= 1000, ErrorCode::TaxTooHigh);
+        require!(house_edge_bps <= 500, ErrorCode::HouseEdgeTooHigh);
 
         // ---- Config ----
         let config = &mut ctx.accounts.config;
         config.admin = admin;
+        config.pending_admin = Pubkey::default();
+        config.paused = 0;
+        // Every role starts with the super-admin, who hands them out afterwards
+        config.operator = admin;
+        config.resolver = admin;
+        config.treasurer = admin;
+        config.pauser = admin;
         config.vault = ctx.accounts.vault.key();
-        config.treasury = treasury;
+        config.vault_bump = ctx.bumps.vault;
+        config.treasury = ctx.accounts.treasury.key();
+        config.treasury_bump = ctx.bumps.treasury;
         config.tax_bps = tax_bps;
+        config.seed_chain = Pubkey::default();
+        config.house_edge_bps = house_edge_bps;
+        config.total_liability = 0;
+        config.max_payout_per_round = 0;
+        config.total_user_balances = 0;
+        // Timelock every admin vault withdrawal until the super-admin sets a threshold
+        config.withdraw_timelock_threshold = 0;
+        config.withdraw_timelock_secs = DEFAULT_WITHDRAW_TIMELOCK_SECS;
+        config.pending_withdrawals = 0;
+        config.withdrawal_count = 0;
+        config.multisig_enabled = false;
+        config.pending_timelock_threshold = 0;
+        config.pending_timelock_secs = 0;
+        config.timelock_change_at = 0;
+        config.pending_tax = 0;
+        config.withdrawn_in_window = 0;
+        config.window_start = 0;
 
         // ---- Game Index (singleton) ----
         let index = &mut ctx.accounts.game_index;
         index.total_games = 0;
         index.bump = ctx.bumps.game_index;
 
+        // ---- Vault & treasury PDAs must be rent exempt before they hold funds ----
+        fund_rent_exempt(&ctx.accounts.signer, &ctx.accounts.vault, &ctx.accounts.system_program)?;
+        fund_rent_exempt(&ctx.accounts.signer, &ctx.accounts.treasury, &ctx.accounts.system_program)?;
+
         Ok(())
     }
 
@@ -39,7 +78,9 @@ pub mod crash_game {
     pub fn create_user(ctx: Context<CreateUser>) -> Result<()> {
         let user = &mut ctx.accounts.user;
         user.balance = 0;
-        user.has_active_bet = false;
+        user.open_bets = 0;
+        user.wallet = ctx.accounts.user_wallet.key();
+        user.mint = Pubkey::default();
         Ok(())
     }
 
@@ -47,6 +88,7 @@ pub mod crash_game {
     // 3. DEPOSIT
     // =================================================
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_DEPOSITS)?;
         require!(amount > 0, ErrorCode::InvalidAmount);
 
         system_transfer(
@@ -57,6 +99,8 @@ pub mod crash_game {
         )?;
 
         ctx.accounts.user.balance = checked_add(ctx.accounts.user.balance, amount)?;
+        ctx.accounts.config.total_user_balances =
+            checked_add(ctx.accounts.config.total_user_balances, amount)?;
         Ok(())
     }
 
@@ -64,19 +108,23 @@ pub mod crash_game {
     // 4. WITHDRAW
     // =================================================
     pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        // Only PAUSE_WITHDRAWALS blocks this: idle balances stay withdrawable while betting is paused
+        require_not_paused(&ctx.accounts.config, PAUSE_WITHDRAWALS)?;
         let user = &mut ctx.accounts.user;
         require!(amount > 0, ErrorCode::InvalidAmount);
         require!(user.balance >= amount, ErrorCode::InsufficientBalance);
-        require!(!user.has_active_bet, ErrorCode::ActiveBetExists);
 
-        system_transfer(
+        pda_transfer(
             &ctx.accounts.vault,
             &ctx.accounts.user_wallet,
             amount,
             &ctx.accounts.system_program,
+            &[b"vault", &[ctx.accounts.config.vault_bump]],
         )?;
 
         user.balance = checked_sub(user.balance, amount)?;
+        ctx.accounts.config.total_user_balances =
+            checked_sub(ctx.accounts.config.total_user_balances, amount)?;
         Ok(())
     }
 
@@ -87,54 +135,111 @@ pub mod crash_game {
         ctx: Context<CreateGame>,
         multiplier: u64,
         game_name: String,
-        created_at: u32,
+        seed_commitment: Option<[u8; 32]>,
+        betting_slots: u64,
+        limits: BetLimits,
     ) -> Result<()> {
-        require!(multiplier >= 100 && multiplier <= 10_000, ErrorCode::InvalidMultiplier);
+        require_role(&ctx.accounts.config, Role::Operator, &ctx.accounts.signer.key())?;
+        require_not_paused(&ctx.accounts.config, PAUSE_NEW_GAMES)?;
+        require!(
+            (MIN_MULTIPLIER..=MAX_MULTIPLIER).contains(&multiplier),
+            ErrorCode::InvalidMultiplier
+        );
+        // At least one slot so the entropy slot always lands after the commitment
+        require!(
+            (1..=MAX_BETTING_SLOTS).contains(&betting_slots),
+            ErrorCode::InvalidBettingWindow
+        );
+        require!(
+            limits.max_bet == 0 || limits.min_bet <= limits.max_bet,
+            ErrorCode::InvalidBetLimits
+        );
         require!(!game_name.is_empty(), ErrorCode::InvalidGameName);
         require!(game_name.len() <= 32, ErrorCode::GameNameTooLong);
 
-        // ---- PDA validation ----
-        let (expected_pda, expected_bump) = Pubkey::find_program_address(
-            &[b"game", &created_at.to_le_bytes()],
-            ctx.program_id,
-        );
-        require_keys_eq!(ctx.accounts.game_state.key(), expected_pda, ErrorCode::InvalidPda);
-        require_eq!(ctx.bumps.game_state, expected_bump, ErrorCode::InvalidPda);
-
         // ---- Initialise GameState ----
+        let clock = Clock::get()?;
         let game = &mut ctx.accounts.game_state;
         game.multiplier = multiplier;
         game.active = true;
-        game.created_at = created_at as i64;
+        game.created_at = clock.unix_timestamp;
         game.resolved_at = 0;
         game.total_bets = 0;
         game.total_volume = 0;
         game.game_name = game_name.clone();
         game.admin = ctx.accounts.signer.key();
         game.crashed = false;
-        game.game_id = game.key();
+        game.game_pda = game.key();
+        game.game_id = ctx.accounts.game_index.total_games;
+        game.min_bet = limits.min_bet;
+        game.max_bet = limits.max_bet;
+        game.max_total_volume = limits.max_total_volume;
+
+        // ---- Provably fair commitment ----
+        // Either a per-game commitment, or the next round of the seed chain.
+        match seed_commitment {
+            Some(commitment) => {
+                game.seed_commitment = commitment;
+                game.chain_round = None;
+            }
+            None => {
+                let chain = ctx
+                    .accounts
+                    .seed_chain
+                    .as_mut()
+                    .ok_or(ErrorCode::SeedChainNotSet)?;
+                require_keys_eq!(chain.key(), ctx.accounts.config.seed_chain, ErrorCode::SeedChainNotSet);
+                require!(chain.next_round < chain.length, ErrorCode::SeedChainExhausted);
+
+                game.seed_commitment = [0u8; 32];
+                game.chain_round = Some(chain.next_round);
+                chain.next_round = checked_add(chain.next_round, 1)?;
+            }
+        }
+        game.server_seed = [0u8; 32];
+        game.entropy_slot = 0;
+        game.entropy_hash = [0u8; 32];
+        game.crash_point = 0;
+        game.revealed = false;
+        game.seed_published = false;
+        game.house_edge_bps = ctx.accounts.config.house_edge_bps;
+        game.liability = 0;
+        game.open_bets = 0;
+        game.tax_collected = 0;
+        // SOL unless a TokenVault is supplied
+        game.mint = ctx
+            .accounts
+            .token_vault
+            .as_ref()
+            .map(|vault| vault.mint)
+            .unwrap_or_default();
+
+        // ---- Round lifecycle ----
+        game.phase = RoundPhase::Betting;
+        game.start_slot = checked_add(clock.slot, betting_slots)?;
 
         // -------------------- INDEX UPDATE --------------------
         let index = &mut ctx.accounts.game_index;
-        let chunk_id = index.total_games / MAX_ENTRIES_PER_CHUNK as u64;
+        let chunk_id = index.current_chunk_id();
         let entry = GameEntry {
             game_pda: game.key(),
-            created_at,
+            created_at: game.created_at,
+            crash_point: 0,
+            total_bets: 0,
+            total_volume: 0,
+            crashed: false,
+            archived: false,
         };
 
-        // Load / init chunk
-        let chunk_acc = &ctx.accounts.chunk;
-        let mut chunk = if chunk_acc.data_is_empty() {
-            GameIndexChunk {
-                chunk_id,
-                entries: Vec::with_capacity(MAX_ENTRIES_PER_CHUNK),
-            }
-        } else {
-            GameIndexChunk::try_from_slice(&chunk_acc.data.borrow())?
-        };
+        // Chunk PDA is created (full size) by the first game that lands in it
+        let chunk = &mut ctx.accounts.chunk;
+        if chunk.entries.is_empty() {
+            chunk.chunk_id = chunk_id;
+            chunk.bump = ctx.bumps.chunk;
+        }
         require_eq!(chunk.chunk_id, chunk_id, ErrorCode::InvalidChunk);
+        require!(chunk.entries.len() < MAX_ENTRIES_PER_CHUNK, ErrorCode::InvalidChunk);
         chunk.entries.push(entry);
-        chunk.serialize(&mut &mut chunk_acc.data.borrow_mut()[..])?;
 
         index.total_games = checked_add(index.total_games, 1)?;
         // ----------------------------------------------------------------
@@ -143,153 +248,193 @@ pub mod crash_game {
     }
 
     // =================================================
-    // 6. PLACE BET (with tax) — FIXED payer
+    // 6. PLACE BET (with tax, from internal balance)
     // =================================================
-    pub fn place_bet(ctx: Context<PlaceBet>, amount: u64) -> Result<()> {
-        require!(amount > 0, ErrorCode::InvalidAmount);
+    pub fn place_bet(
+        ctx: Context<PlaceBet>,
+        amount: u64,
+        auto_cashout_multiplier: Option<u64>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require_not_paused(config, PAUSE_BETS)?;
+        require_keys_eq!(ctx.accounts.game_state.mint, Pubkey::default(), ErrorCode::WrongCurrency);
+
+        let (tax, max_payout) = open_bet(
+            &mut ctx.accounts.bet,
+            &mut ctx.accounts.user_balance,
+            &mut ctx.accounts.game_state,
+            config,
+            amount,
+            auto_cashout_multiplier,
+        )?;
 
-        let bet = &mut ctx.accounts.bet;
-        let user = &mut ctx.accounts.user_balance;
-        let game = &mut ctx.accounts.game_state;
-        let config = &ctx.accounts.config;
+        // ---- Ledger & solvency ----
+        // The stake already sits in the vault since `deposit`; only the ledger moves.
+        // Tax stays there too until the round is revealed (collect_game_tax).
+        config.total_user_balances = checked_sub(config.total_user_balances, amount)?;
+        config.pending_tax = checked_add(config.pending_tax, tax)?;
+        config.total_liability = checked_add(config.total_liability, max_payout)?;
+        require_solvent(&ctx.accounts.vault.to_account_info(), config, 0)?;
+
+        Ok(())
+    }
 
+    // =================================================
+    // 7. REVEAL GAME (commit-reveal crash point)
+    // =================================================
+    pub fn reveal_game(ctx: Context<RevealGame>, server_seed: [u8; 32]) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::Resolver, &ctx.accounts.signer.key())?;
+        let game = &mut ctx.accounts.game_state;
         require!(game.active, ErrorCode::GameNotActive);
-        require!(user.balance >= amount, ErrorCode::InsufficientBalance);
-        require!(!user.has_active_bet, ErrorCode::ActiveBetExists);
-
-        // ---- TAX ----
-        let tax_bps = config.tax_bps as u64;
-        let tax = amount
-            .checked_mul(tax_bps)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(10_000)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let bet_after_tax = amount.checked_sub(tax).ok_or(ErrorCode::MathOverflow)?;
+        require!(!game.revealed, ErrorCode::AlreadyRevealed);
 
-        // ---- Transfer full amount to vault ----
-        system_transfer(
-            &ctx.accounts.user_wallet,
-            &ctx.accounts.vault,
-            amount,
-            &ctx.accounts.system_program,
-        )?;
+        let clock = Clock::get()?;
+        require!(
+            round_phase(game, clock.slot) == RoundPhase::Running,
+            ErrorCode::RoundNotRunning
+        );
 
-        // ---- Move tax to treasury ----
-        if tax > 0 {
-            system_transfer(
-                &ctx.accounts.vault,
-                &ctx.accounts.treasury,
-                tax,
-                &ctx.accounts.system_program,
-            )?;
+        match game.chain_round {
+            None => require!(
+                hash(&server_seed).to_bytes() == game.seed_commitment,
+                ErrorCode::InvalidSeed
+            ),
+            Some(round) => {
+                // Each round reveals the previous link: hash(seed) == last_revealed
+                let chain = ctx
+                    .accounts
+                    .seed_chain
+                    .as_mut()
+                    .ok_or(ErrorCode::SeedChainNotSet)?;
+                require_eq!(round, chain.revealed_rounds, ErrorCode::OutOfOrderReveal);
+                require!(
+                    hash(&server_seed).to_bytes() == chain.last_revealed,
+                    ErrorCode::InvalidSeed
+                );
+
+                game.seed_commitment = chain.last_revealed;
+                chain.last_revealed = server_seed;
+                chain.revealed_rounds = checked_add(chain.revealed_rounds, 1)?;
+                chain.closed_rounds = checked_add(chain.closed_rounds, 1)?;
+            }
         }
 
-        // ---- Update user & bet (net amount) ----
-        user.balance = checked_sub(user.balance, amount)?;
-        user.has_active_bet = true;
+        // Entropy nobody knew at commit time: the first slot hash from betting close on
+        let (entropy_slot, entropy_hash) =
+            slot_hash_at_or_after(&ctx.accounts.slot_hashes, game.start_slot)?;
+        let crash_point = derive_crash_point(
+            &server_seed,
+            &game.key(),
+            &entropy_hash,
+            game.house_edge_bps,
+        );
 
-        bet.user = user.key();
-        bet.amount = bet_after_tax;
-        bet.active = true;
-        bet.game_id = game.game_id;
-        bet.payout_amount = 0;
-        bet.claimed = false;
+        // The curve must actually have reached the crash point (or the table cap)
+        require!(
+            game_multiplier_at(game, clock.slot) >= crash_point.min(game.multiplier),
+            ErrorCode::RoundStillRunning
+        );
 
-        game.total_bets = checked_add(game.total_bets, 1)?;
-        game.total_volume = checked_add(game.total_volume, bet_after_tax)?;
+        game.server_seed = server_seed;
+        game.entropy_slot = entropy_slot;
+        game.entropy_hash = entropy_hash;
+        game.crash_point = crash_point;
+        game.revealed = true;
+        game.seed_published = true;
+        game.active = false;
+        game.crashed = crash_point < game.multiplier;
+        game.phase = RoundPhase::Crashed;
+        game.resolved_at = clock.unix_timestamp;
 
         Ok(())
     }
 
     // =================================================
-    // 7. RESOLVE GAME
+    // 8. RESOLVE GAME (settle bets against revealed outcome)
     // =================================================
-    pub fn resolve_game(ctx: Context<ResolveGame>, crashed: bool) -> Result<()> {
+    // Batch convenience only: claim_bet settles any bet left out here.
+    pub fn resolve_game(ctx: Context<ResolveGame>) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::Resolver, &ctx.accounts.signer.key())?;
         let game = &mut ctx.accounts.game_state;
-        require!(game.active, ErrorCode::GameNotActive);
+        require!(game.revealed, ErrorCode::GameNotRevealed);
+        let book = liability_book(
+            game,
+            &mut ctx.accounts.config,
+            ctx.accounts.token_vault.as_deref_mut(),
+        )?;
 
-        game.active = false;
-        game.crashed = crashed;
-        game.resolved_at = Clock::get()?.unix_timestamp;
-
-        if !crashed {
-            for bet_acc in ctx.remaining_accounts.iter() {
-                let mut data = bet_acc.try_borrow_mut_data()?;
-                if data.len() < std::mem::size_of::<Bet>() { continue; }
-                let mut bet = match Bet::try_deserialize(&mut &data[..]) {
-                    Ok(b) => b,
-                    Err(_) => continue,
-                };
-                if bet.active && bet.game_id == game.game_id {
-                    let payout = bet.amount
-                        .checked_mul(game.multiplier)
-                        .ok_or(ErrorCode::MathOverflow)?
-                        .checked_div(100)
-                        .ok_or(ErrorCode::MathOverflow)?;
-                    bet.active = false;
-                    bet.payout_amount = payout;
-                    bet.serialize(&mut &mut data[..])?;
-                }
-            }
-        } else {
-            for bet_acc in ctx.remaining_accounts.iter() {
-                let mut data = bet_acc.try_borrow_mut_data()?;
-                if data.len() < std::mem::size_of::<Bet>() { continue; }
-                let mut bet = match Bet::try_deserialize(&mut &data[..]) {
-                    Ok(b) => b,
-                    Err(_) => continue,
-                };
-                if bet.active && bet.game_id == game.game_id {
-                    bet.active = false;
-                    bet.payout_amount = 0;
-                    bet.serialize(&mut &mut data[..])?;
-                }
+        for bet_acc in ctx.remaining_accounts.iter() {
+            if bet_acc.owner != ctx.program_id { continue; }
+            let mut data = bet_acc.try_borrow_mut_data()?;
+            let mut bet = match Bet::try_deserialize(&mut &data[..]) {
+                Ok(b) => b,
+                Err(_) => continue,
+            };
+            if bet.active && bet.game_pda == game.game_pda {
+                settle_open_bet(game, book, &mut bet)?;
+                bet.try_serialize(&mut &mut data[..])?;
             }
         }
         Ok(())
     }
 
     // =================================================
-    // 8. CLAIM PAYOUT
+    // 9. CLAIM PAYOUT
     // =================================================
     pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
-        let bet = &mut ctx.accounts.bet;
-        let user = &mut ctx.accounts.user_balance;
-
-        require!(!bet.active, ErrorCode::BetStillActive);
-        require!(!bet.claimed, ErrorCode::AlreadyClaimed);
-        require!(bet.payout_amount > 0, ErrorCode::NoPayout);
-        require_keys_eq!(bet.user, user.key(), ErrorCode::Unauthorized);
-
-        user.balance = checked_add(user.balance, bet.payout_amount)?;
-        user.has_active_bet = false;
-        bet.claimed = true;
-
-        Ok(())
+        require_keys_eq!(ctx.accounts.game_state.mint, Pubkey::default(), ErrorCode::WrongCurrency);
+        let config: &mut Config = &mut ctx.accounts.config;
+
+        claim_bet(
+            &mut ctx.accounts.bet,
+            &mut ctx.accounts.user_balance,
+            &mut ctx.accounts.game_state,
+            &mut config.total_liability,
+            &mut config.total_user_balances,
+            &mut config.pending_tax,
+        )
     }
 
     // =================================================
-    // 9. ADMIN WITHDRAW (game vault)
+    // 10. ADMIN WITHDRAW (game vault)
     // =================================================
     pub fn admin_withdraw(ctx: Context<AdminWithdraw>, amount: u64) -> Result<()> {
         require!(amount > 0, ErrorCode::InvalidAmount);
-        require_keys_eq!(ctx.accounts.signer.key(), ctx.accounts.config.admin, ErrorCode::Unauthorized);
+        require_role(&ctx.accounts.config, Role::Treasurer, &ctx.accounts.signer.key())?;
+        require!(!ctx.accounts.config.multisig_enabled, ErrorCode::MultisigRequired);
 
-        system_transfer(
+        // The threshold caps the running total per window, so repeated calls
+        // can't add up to more than one untimelocked withdrawal
+        let config = &mut ctx.accounts.config;
+        let now = Clock::get()?.unix_timestamp;
+        if now >= config.window_start.saturating_add(config.withdraw_timelock_secs) {
+            config.window_start = now;
+            config.withdrawn_in_window = 0;
+        }
+        let withdrawn = checked_add(config.withdrawn_in_window, amount)?;
+        require!(
+            withdrawn <= config.withdraw_timelock_threshold,
+            ErrorCode::TimelockRequired
+        );
+        config.withdrawn_in_window = withdrawn;
+        require_solvent(&ctx.accounts.vault.to_account_info(), &ctx.accounts.config, amount)?;
+
+        pda_transfer(
             &ctx.accounts.vault,
             &ctx.accounts.signer,
             amount,
             &ctx.accounts.system_program,
+            &[b"vault", &[ctx.accounts.config.vault_bump]],
         )?;
         Ok(())
     }
 
     // =================================================
-    // 10. ADMIN DEPOSIT BOUNTY
+    // 11. ADMIN DEPOSIT BOUNTY
     // =================================================
     pub fn admin_deposit_bounty(ctx: Context<AdminDepositBounty>, amount: u64) -> Result<()> {
         require!(amount > 0, ErrorCode::InvalidAmount);
-        require_keys_eq!(ctx.accounts.signer.key(), ctx.accounts.config.admin, ErrorCode::Unauthorized);
+        require_role(&ctx.accounts.config, Role::Treasurer, &ctx.accounts.signer.key())?;
 
         system_transfer(
             &ctx.accounts.signer,
@@ -301,375 +446,2676 @@ pub mod crash_game {
     }
 
     // =================================================
-    // 11. ADMIN SET TAX
+    // 12. ADMIN SET TAX
     // =================================================
     pub fn set_tax(ctx: Context<SetTax>, tax_bps: u16) -> Result<()> {
-        require_keys_eq!(ctx.accounts.signer.key(), ctx.accounts.config.admin, ErrorCode::Unauthorized);
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        require!(!ctx.accounts.config.multisig_enabled, ErrorCode::MultisigRequired);
         require!(tax_bps <= 1000, ErrorCode::TaxTooHigh);
         ctx.accounts.config.tax_bps = tax_bps;
         Ok(())
     }
 
     // =================================================
-    // 12. ADMIN WITHDRAW TREASURY
+    // 13. ADMIN WITHDRAW TREASURY
     // =================================================
     pub fn admin_withdraw_treasury(ctx: Context<AdminWithdrawTreasury>, amount: u64) -> Result<()> {
         require!(amount > 0, ErrorCode::InvalidAmount);
-        require_keys_eq!(ctx.accounts.signer.key(), ctx.accounts.config.admin, ErrorCode::Unauthorized);
+        require_role(&ctx.accounts.config, Role::Treasurer, &ctx.accounts.signer.key())?;
+        require!(!ctx.accounts.config.multisig_enabled, ErrorCode::MultisigRequired);
 
-        system_transfer(
+        treasury_withdraw(
             &ctx.accounts.treasury,
             &ctx.accounts.signer,
             amount,
             &ctx.accounts.system_program,
-        )?;
-        Ok(())
+            ctx.accounts.config.treasury_bump,
+        )
     }
 
     // =================================================
-    // 13. LIST GAMES (paginated)
+    // 14. LIST GAMES (paginated)
     // =================================================
+    // Scans index positions [offset, offset + limit), oldest or newest first,
+    // and returns the entries matching `filter`. remaining_accounts are read in
+    // scan order: each chunk PDA when the scan enters it, then the GameState of
+    // every non-archived entry visited in that chunk.
     pub fn list_games(
         ctx: Context<ListGames>,
         offset: u64,
         limit: u8,
+        filter: GameListFilter,
+        newest_first: bool,
     ) -> Result<Vec<GameListItem>> {
-        let index = &ctx.accounts.game_index;
+        require!(limit as usize <= MAX_LIST_GAMES, ErrorCode::ListLimitTooHigh);
+
+        let total = ctx.accounts.game_index.total_games;
+        let end = offset.saturating_add(limit as u64).min(total);
+        let mut accounts = ctx.remaining_accounts.iter();
+        let mut chunk: Option<GameIndexChunk> = None;
         let mut out = Vec::with_capacity(limit as usize);
-        let mut remaining = limit as usize;
-        let mut cur = offset;
-
-        while remaining > 0 && cur < index.total_games {
-            let chunk_id = cur / MAX_ENTRIES_PER_CHUNK as u64;
-            let idx_in_chunk = (cur % MAX_ENTRIES_PER_CHUNK as u64) as usize;
-
-            let chunk_acc = &ctx.remaining_accounts[chunk_id as usize];
-            let chunk = GameIndexChunk::try_from_slice(&chunk_acc.data.borrow())?;
-            let entry = chunk.entries[idx_in_chunk];
-
-            let game_acc = &ctx.remaining_accounts[ctx.remaining_accounts.len() / 2 + chunk_id as usize];
-            let game = GameState::try_from_slice(&game_acc.data.borrow())?;
-
-            out.push(GameListItem {
-                game_pda: entry.game_pda,
-                created_at: entry.created_at,
-                game_name: game.game_name.clone(),
-                multiplier: game.multiplier,
-                active: game.active,
-                crashed: game.crashed,
-            });
-
-            remaining -= 1;
-            cur += 1;
+
+        for pos in offset..end {
+            let game_no = if newest_first { total - 1 - pos } else { pos };
+            let chunk_id = game_no / MAX_ENTRIES_PER_CHUNK as u64;
+            let idx_in_chunk = (game_no % MAX_ENTRIES_PER_CHUNK as u64) as usize;
+
+            let current = match chunk.take() {
+                Some(current) if current.chunk_id == chunk_id => current,
+                _ => {
+                    let chunk_acc = accounts.next().ok_or(ErrorCode::MissingListAccount)?;
+                    load_index_chunk(chunk_acc, chunk_id, ctx.program_id)?
+                }
+            };
+            let entry = *current.entries.get(idx_in_chunk).ok_or(ErrorCode::InvalidChunk)?;
+            chunk = Some(current);
+
+            let item = if entry.archived {
+                // GameState is closed; list it from the summary left in the chunk
+                GameListItem {
+                    game_id: game_no,
+                    game_pda: entry.game_pda,
+                    created_at: entry.created_at,
+                    game_name: String::new(),
+                    multiplier: 0,
+                    active: false,
+                    crashed: entry.crashed,
+                    crash_point: entry.crash_point,
+                    archived: true,
+                }
+            } else {
+                // entry.game_pda was derived from the game seeds in create_game
+                let game_acc = accounts.next().ok_or(ErrorCode::MissingListAccount)?;
+                require_keys_eq!(game_acc.key(), entry.game_pda, ErrorCode::InvalidPda);
+                require_keys_eq!(*game_acc.owner, *ctx.program_id, ErrorCode::InvalidPda);
+                let game = GameState::try_deserialize(&mut &game_acc.data.borrow()[..])?;
+
+                GameListItem {
+                    game_id: game_no,
+                    game_pda: entry.game_pda,
+                    created_at: entry.created_at,
+                    game_name: game.game_name,
+                    multiplier: game.multiplier,
+                    active: game.active,
+                    crashed: game.crashed,
+                    // crash_point is clamped to MAX_MULTIPLIER, so it fits a u16
+                    crash_point: game.crash_point as u16,
+                    archived: false,
+                }
+            };
+
+            if filter.matches(&item) {
+                out.push(item);
+            }
         }
         Ok(out)
     }
 
     // =================================================
-    // 14. GET GAME BY TIMESTAMP
+    // 15. GET GAME BY ID
     // =================================================
-      pub fn get_game_by_timestamp(
-        ctx: Context<GetGameByTimestamp>,
-        _created_at: u32,
-    ) -> Result<GameState> {
+    pub fn get_game_by_id(ctx: Context<GetGameById>, _game_id: u64) -> Result<GameState> {
         Ok((*ctx.accounts.game_state).clone())
     }
-}
-
-// =================================================
-// ACCOUNT STRUCTS
-// =================================================
-#[account]
-pub struct Config {
-    pub admin: Pubkey,
-    pub vault: Pubkey,
-    pub treasury: Pubkey,
-    pub tax_bps: u16,
-}
 
-#[account]
-pub struct UserBalance {
-    pub balance: u64,
-    pub has_active_bet: bool,
-}
+    // =================================================
+    // 16. INIT SEED CHAIN (hash-chain commitment)
+    // =================================================
+    pub fn init_seed_chain(
+        ctx: Context<InitSeedChain>,
+        terminal_hash: [u8; 32],
+        length: u64,
+    ) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::Resolver, &ctx.accounts.signer.key())?;
+        require!(length > 0, ErrorCode::InvalidAmount);
+
+        let chain = &mut ctx.accounts.seed_chain;
+        chain.terminal_hash = terminal_hash;
+        chain.last_revealed = terminal_hash;
+        chain.length = length;
+        chain.next_round = 0;
+        chain.revealed_rounds = 0;
+        chain.closed_rounds = 0;
+        chain.bump = ctx.bumps.seed_chain;
+
+        ctx.accounts.config.seed_chain = chain.key();
+        Ok(())
+    }
 
-#[account]
-pub struct GameState {
-    pub multiplier: u64,
-    pub active: bool,
-    pub created_at: i64,
-    pub resolved_at: i64,
-    pub total_bets: u64,
-    pub total_volume: u64,
-    pub game_name: String,
-    pub admin: Pubkey,
-    pub crashed: bool,
-    pub game_id: Pubkey,
-}
+    // =================================================
+    // 17. ROTATE SEED CHAIN (once every issued round is over)
+    // =================================================
+    // Unissued rounds of the old chain are simply abandoned.
+    pub fn rotate_seed_chain(
+        ctx: Context<RotateSeedChain>,
+        terminal_hash: [u8; 32],
+        length: u64,
+    ) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::Resolver, &ctx.accounts.signer.key())?;
+        require!(length > 0, ErrorCode::InvalidAmount);
+
+        let chain = &mut ctx.accounts.seed_chain;
+        require_eq!(chain.closed_rounds, chain.next_round, ErrorCode::SeedChainInUse);
+
+        chain.terminal_hash = terminal_hash;
+        chain.last_revealed = terminal_hash;
+        chain.length = length;
+        chain.next_round = 0;
+        chain.revealed_rounds = 0;
+        chain.closed_rounds = 0;
+        Ok(())
+    }
 
-#[account]
-pub struct Bet {
-    pub user: Pubkey,
-    pub amount: u64,
-    pub active: bool,
-    pub game_id: Pubkey,
-    pub payout_amount: u64,
-    pub claimed: bool,
-}
+    // =================================================
+    // 18. CASH OUT (at live multiplier)
+    // =================================================
+    pub fn cash_out(ctx: Context<CashOut>) -> Result<()> {
+        let bet = &mut ctx.accounts.bet;
+        let game = &mut ctx.accounts.game_state;
 
-// ----------------- INDEX -----------------
-#[account]
-pub struct GameIndex {
-    pub total_games: u64,
-    pub bump: u8,
-}
+        require!(bet.active, ErrorCode::BetAlreadySettled);
+        require!(game.active, ErrorCode::GameNotActive);
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub struct GameEntry {
-    pub game_pda: Pubkey,
-    pub created_at: u32,
-}
+        let slot = Clock::get()?.slot;
+        require!(round_phase(game, slot) == RoundPhase::Running, ErrorCode::RoundNotRunning);
 
-#[account]
-pub struct GameIndexChunk {
-    pub chunk_id: u64,
-    pub entries: Vec<GameEntry>,
-}
+        // An auto cash-out target already passed on the curve wins over a late manual one
+        let mut multiplier = game_multiplier_at(game, slot);
+        if bet.auto_cashout_multiplier > 0 {
+            multiplier = multiplier.min(bet.auto_cashout_multiplier);
+        }
+        let payout = payout_at(bet.amount, multiplier)?;
+        let max_payout = payout_at(bet.amount, bet_target(game, bet.auto_cashout_multiplier))?;
+        let book = liability_book(
+            game,
+            &mut ctx.accounts.config,
+            ctx.accounts.token_vault.as_deref_mut(),
+        )?;
+        release_liability(game, book, max_payout.saturating_sub(payout))?;
 
-pub const MAX_ENTRIES_PER_CHUNK: usize = 200;
+        bet.active = false;
+        bet.cashout_multiplier = multiplier;
+        bet.payout_amount = payout;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct GameListItem {
-    pub game_pda: Pubkey,
-    pub created_at: u32,
-    pub game_name: String,
-    pub multiplier: u64,
-    pub active: bool,
-    pub crashed: bool,
-}
+        Ok(())
+    }
 
-// =================================================
-// CONTEXTS
-// =================================================
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = signer,
-        space = 8 + 32 + 32 + 32 + 2,
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: Account<'info, Config>,
+    // =================================================
+    // 19. ADMIN SET HOUSE EDGE
+    // =================================================
+    pub fn set_house_edge(ctx: Context<SetHouseEdge>, house_edge_bps: u16) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        require!(house_edge_bps <= 500, ErrorCode::HouseEdgeTooHigh);
+        ctx.accounts.config.house_edge_bps = house_edge_bps;
+        Ok(())
+    }
 
-    #[account(
-        init,
-        payer = signer,
-        space = 8 + 8 + 1,
-        seeds = [b"game_index"],
-        bump
-    )]
-    pub game_index: Account<'info, GameIndex>,
+    // =================================================
+    // 20. ADMIN SET MAX PAYOUT PER ROUND
+    // =================================================
+    pub fn set_max_payout_per_round(ctx: Context<SetMaxPayoutPerRound>, max_payout: u64) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        ctx.accounts.config.max_payout_per_round = max_payout;
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub signer: Signer<'info>,
+    // =================================================
+    // 21. AUDIT INVARIANTS (ledger vs vault)
+    // =================================================
+    // remaining_accounts: any mix of UserBalance and Bet accounts. Audits the SOL
+    // ledger, or the mint's ledger when token_vault + vault_token_account are
+    // passed; accounts in other currencies are skipped.
+    pub fn audit_invariants(ctx: Context<AuditInvariants>) -> Result<AuditReport> {
+        let config = &ctx.accounts.config;
+        let (mint, total_user_balances, total_liability, owed, vault_free) =
+            match (&ctx.accounts.token_vault, &ctx.accounts.vault_token_account) {
+                (None, None) => {
+                    let vault = ctx.accounts.vault.to_account_info();
+                    let rent = Rent::get()?.minimum_balance(vault.data_len());
+                    (
+                        Pubkey::default(),
+                        config.total_user_balances,
+                        config.total_liability,
+                        sol_owed(config)?,
+                        vault.lamports().saturating_sub(rent),
+                    )
+                }
+                (Some(token_vault), Some(vault_token_account)) => {
+                    require_keys_eq!(
+                        vault_token_account.key(),
+                        token_vault.vault_token_account,
+                        ErrorCode::InvalidAuditAccount
+                    );
+                    (
+                        token_vault.mint,
+                        token_vault.total_user_balances,
+                        token_vault.total_liability,
+                        token_owed(token_vault)?,
+                        vault_token_account.amount,
+                    )
+                }
+                _ => return err!(ErrorCode::InvalidAuditAccount),
+            };
+
+        let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut user_balances: u64 = 0;
+        let mut outstanding_payouts: u64 = 0;
+
+        for acc in ctx.remaining_accounts.iter() {
+            require_keys_eq!(*acc.owner, *ctx.program_id, ErrorCode::InvalidAuditAccount);
+            require!(!seen.contains(acc.key), ErrorCode::InvalidAuditAccount);
+            seen.push(acc.key());
+
+            let data = acc.try_borrow_data()?;
+            if let Ok(user) = UserBalance::try_deserialize(&mut &data[..]) {
+                if user.mint == mint {
+                    user_balances = checked_add(user_balances, user.balance)?;
+                }
+            } else if let Ok(bet) = Bet::try_deserialize(&mut &data[..]) {
+                if bet.mint == mint && !bet.active && !bet.claimed {
+                    outstanding_payouts = checked_add(outstanding_payouts, bet.payout_amount)?;
+                }
+            } else {
+                return err!(ErrorCode::InvalidAuditAccount);
+            }
+        }
 
-    #[account(mut)]
-    pub vault: AccountInfo<'info>,
+        // The supplied subset can never exceed the tracked totals...
+        require!(user_balances <= total_user_balances, ErrorCode::InvariantViolated);
+        require!(outstanding_payouts <= total_liability, ErrorCode::InvariantViolated);
+
+        // ...and everything the solvency checks count must be backed by the vault.
+        require!(owed <= vault_free, ErrorCode::InvariantViolated);
+
+        Ok(AuditReport {
+            mint,
+            user_balances,
+            outstanding_payouts,
+            total_user_balances,
+            total_liability,
+            vault_free,
+            owed,
+        })
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    // =================================================
+    // 22. GRANT ROLE
+    // =================================================
+    pub fn grant_role(ctx: Context<ManageRole>, role: Role, holder: Pubkey) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        require!(role != Role::SuperAdmin, ErrorCode::InvalidRole);
+        require_keys_neq!(holder, Pubkey::default(), ErrorCode::InvalidRole);
+        set_role_holder(&mut ctx.accounts.config, role, holder);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct CreateUser<'info> {
-    #[account(
-        init,
-        payer = user_wallet,
-        space = 8 + 8 + 1,
-        seeds = [b"user_balance", user_wallet.key().as_ref()],
-        bump
-    )]
-    pub user: Account<'info, UserBalance>,
+    // =================================================
+    // 23. REVOKE ROLE
+    // =================================================
+    pub fn revoke_role(ctx: Context<ManageRole>, role: Role) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        require!(role != Role::SuperAdmin, ErrorCode::InvalidRole);
+        set_role_holder(&mut ctx.accounts.config, role, Pubkey::default());
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub user_wallet: Signer<'info>,
+    // =================================================
+    // 24. PROPOSE ADMIN (two-step transfer)
+    // =================================================
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        require_keys_neq!(new_admin, Pubkey::default(), ErrorCode::InvalidRole);
+        ctx.accounts.config.pending_admin = new_admin;
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    // =================================================
+    // 25. ACCEPT ADMIN (signed by the pending admin)
+    // =================================================
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require_keys_neq!(config.pending_admin, Pubkey::default(), ErrorCode::NoPendingAdmin);
+        require_keys_eq!(ctx.accounts.signer.key(), config.pending_admin, ErrorCode::Unauthorized);
 
-#[derive(Accounts)]
-pub struct Deposit<'info> {
-    #[account(mut)]
-    pub user: Account<'info, UserBalance>,
+        config.admin = config.pending_admin;
+        config.pending_admin = Pubkey::default();
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub user_wallet: Signer<'info>,
+    // =================================================
+    // 26. CANCEL ADMIN TRANSFER
+    // =================================================
+    pub fn cancel_admin_transfer(ctx: Context<ProposeAdmin>) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        require_keys_neq!(ctx.accounts.config.pending_admin, Pubkey::default(), ErrorCode::NoPendingAdmin);
+        ctx.accounts.config.pending_admin = Pubkey::default();
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub vault: AccountInfo<'info>,
+    // =================================================
+    // 27. SET PAUSED (circuit breaker)
+    // =================================================
+    pub fn set_paused(ctx: Context<SetPaused>, paused: u8) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::Pauser, &ctx.accounts.signer.key())?;
+        require!(paused & !PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags);
+        ctx.accounts.config.paused = paused;
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    // =================================================
+    // 28. ADMIN SET WITHDRAW TIMELOCK
+    // =================================================
+    pub fn set_withdraw_timelock(
+        ctx: Context<SetWithdrawTimelock>,
+        threshold: u64,
+        delay_secs: i64,
+    ) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        require!(
+            (0..=MAX_WITHDRAW_TIMELOCK_SECS).contains(&delay_secs),
+            ErrorCode::InvalidTimelock
+        );
+        let config = &mut ctx.accounts.config;
 
-#[derive(Accounts)]
-pub struct Withdraw<'info> {
-    #[account(mut)]
-    pub user: Account<'info, UserBalance>,
+        // Tightening applies at once (and drops any queued loosening). Anything
+        // that loosens waits out the current delay, so it can't be combined with
+        // an admin_withdraw in the same transaction.
+        if threshold <= config.withdraw_timelock_threshold
+            && delay_secs >= config.withdraw_timelock_secs
+        {
+            config.withdraw_timelock_threshold = threshold;
+            config.withdraw_timelock_secs = delay_secs;
+            config.timelock_change_at = 0;
+        } else {
+            config.pending_timelock_threshold = threshold;
+            config.pending_timelock_secs = delay_secs;
+            config.timelock_change_at = Clock::get()?
+                .unix_timestamp
+                .checked_add(config.withdraw_timelock_secs)
+                .ok_or(ErrorCode::MathOverflow)?
+                .max(1);
+        }
+        Ok(())
+    }
 
-    #[account(mut)]
+    // =================================================
+    // 29. QUEUE ADMIN WITHDRAW (timelocked)
+    // =================================================
+    pub fn queue_admin_withdraw(ctx: Context<QueueAdminWithdraw>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require_role(&ctx.accounts.config, Role::Treasurer, &ctx.accounts.signer.key())?;
+        require!(!ctx.accounts.config.multisig_enabled, ErrorCode::MultisigRequired);
+
+        let config = &mut ctx.accounts.config;
+        // Queued amounts are reserved immediately so bets can't eat into them
+        config.pending_withdrawals = checked_add(config.pending_withdrawals, amount)?;
+        require_solvent(&ctx.accounts.vault.to_account_info(), config, 0)?;
+
+        let pending = &mut ctx.accounts.pending_withdrawal;
+        pending.id = config.withdrawal_count;
+        pending.amount = amount;
+        pending.recipient = ctx.accounts.signer.key();
+        pending.mint = Pubkey::default();
+        pending.unlock_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(config.withdraw_timelock_secs)
+            .ok_or(ErrorCode::MathOverflow)?;
+        pending.bump = ctx.bumps.pending_withdrawal;
+
+        config.withdrawal_count = checked_add(config.withdrawal_count, 1)?;
+        Ok(())
+    }
+
+    // =================================================
+    // 30. EXECUTE ADMIN WITHDRAW (after unlock)
+    // =================================================
+    pub fn execute_admin_withdraw(ctx: Context<ExecuteAdminWithdraw>) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::Treasurer, &ctx.accounts.signer.key())?;
+        require!(!ctx.accounts.config.multisig_enabled, ErrorCode::MultisigRequired);
+
+        let pending = &ctx.accounts.pending_withdrawal;
+        require_keys_eq!(pending.mint, Pubkey::default(), ErrorCode::WrongCurrency);
+        require!(
+            Clock::get()?.unix_timestamp >= pending.unlock_at,
+            ErrorCode::TimelockNotExpired
+        );
+
+        let config = &mut ctx.accounts.config;
+        config.pending_withdrawals = checked_sub(config.pending_withdrawals, pending.amount)?;
+        require_solvent(&ctx.accounts.vault.to_account_info(), config, pending.amount)?;
+
+        pda_transfer(
+            &ctx.accounts.vault,
+            &ctx.accounts.recipient,
+            pending.amount,
+            &ctx.accounts.system_program,
+            &[b"vault", &[config.vault_bump]],
+        )?;
+        Ok(())
+    }
+
+    // =================================================
+    // 31. CANCEL ADMIN WITHDRAW
+    // =================================================
+    pub fn cancel_admin_withdraw(ctx: Context<CancelAdminWithdraw>) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let config = &mut ctx.accounts.config;
+        require!(
+            signer == role_holder(config, Role::Treasurer)
+                || signer == role_holder(config, Role::SuperAdmin),
+            ErrorCode::Unauthorized
+        );
+
+        let pending = &ctx.accounts.pending_withdrawal;
+        if pending.mint == Pubkey::default() {
+            config.pending_withdrawals = checked_sub(config.pending_withdrawals, pending.amount)?;
+        } else {
+            let token_vault = ctx
+                .accounts
+                .token_vault
+                .as_deref_mut()
+                .ok_or(ErrorCode::WrongCurrency)?;
+            token_vault.pending_withdrawals =
+                checked_sub(token_vault.pending_withdrawals, pending.amount)?;
+        }
+        Ok(())
+    }
+
+    // =================================================
+    // 32. CREATE MULTISIG (enables multisig mode)
+    // =================================================
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        require!(
+            !owners.is_empty() && owners.len() <= MAX_MULTISIG_OWNERS,
+            ErrorCode::InvalidMultisig
+        );
+        require!(
+            threshold > 0 && threshold as usize <= owners.len(),
+            ErrorCode::InvalidMultisig
+        );
+        for (i, owner) in owners.iter().enumerate() {
+            require!(!owners[..i].contains(owner), ErrorCode::InvalidMultisig);
+        }
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.owners = owners;
+        multisig.threshold = threshold;
+        multisig.proposal_count = 0;
+        multisig.bump = ctx.bumps.multisig;
+
+        // One-way: from here on treasury/vault movements and tax changes need M-of-N
+        ctx.accounts.config.multisig_enabled = true;
+        Ok(())
+    }
+
+    // =================================================
+    // 33. PROPOSE (multisig action)
+    // =================================================
+    pub fn propose(
+        ctx: Context<Propose>,
+        action: ProposalAction,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        let owner_index = multisig_owner_index(multisig, &ctx.accounts.signer.key())?;
+
+        match action {
+            ProposalAction::AdminWithdraw { amount }
+            | ProposalAction::AdminWithdrawTreasury { amount }
+            | ProposalAction::AdminWithdrawToken { amount, .. }
+            | ProposalAction::AdminWithdrawTreasuryToken { amount, .. } => {
+                require!(amount > 0, ErrorCode::InvalidAmount);
+                require_keys_neq!(recipient, Pubkey::default(), ErrorCode::InvalidProposal);
+            }
+            ProposalAction::SetTax { tax_bps } => {
+                require!(tax_bps <= 1000, ErrorCode::TaxTooHigh);
+            }
+        }
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = multisig.proposal_count;
+        proposal.action = action;
+        proposal.recipient = recipient;
+        proposal.approvals = 1 << owner_index; // proposer approves
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        multisig.proposal_count = checked_add(multisig.proposal_count, 1)?;
+        Ok(())
+    }
+
+    // =================================================
+    // 34. APPROVE (multisig owner)
+    // =================================================
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        let owner_index = multisig_owner_index(&ctx.accounts.multisig, &ctx.accounts.signer.key())?;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, ErrorCode::ProposalExecuted);
+
+        proposal.approvals |= 1 << owner_index;
+        Ok(())
+    }
+
+    // =================================================
+    // 35. EXECUTE PROPOSAL (once threshold reached)
+    // =================================================
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        multisig_owner_index(multisig, &ctx.accounts.signer.key())?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, ErrorCode::ProposalExecuted);
+        require!(
+            proposal.approvals.count_ones() >= multisig.threshold as u32,
+            ErrorCode::NotEnoughApprovals
+        );
+
+        let config = &mut ctx.accounts.config;
+        match proposal.action {
+            ProposalAction::AdminWithdraw { amount } => {
+                let recipient = ctx.accounts.recipient.as_ref().ok_or(ErrorCode::InvalidProposal)?;
+                require_solvent(&ctx.accounts.vault.to_account_info(), config, amount)?;
+                pda_transfer(
+                    &ctx.accounts.vault,
+                    recipient,
+                    amount,
+                    &ctx.accounts.system_program,
+                    &[b"vault", &[config.vault_bump]],
+                )?;
+            }
+            ProposalAction::AdminWithdrawTreasury { amount } => {
+                let recipient = ctx.accounts.recipient.as_ref().ok_or(ErrorCode::InvalidProposal)?;
+                treasury_withdraw(
+                    &ctx.accounts.treasury,
+                    recipient,
+                    amount,
+                    &ctx.accounts.system_program,
+                    config.treasury_bump,
+                )?;
+            }
+            ProposalAction::SetTax { tax_bps } => {
+                require!(tax_bps <= 1000, ErrorCode::TaxTooHigh);
+                config.tax_bps = tax_bps;
+            }
+            ProposalAction::AdminWithdrawToken { mint, amount }
+            | ProposalAction::AdminWithdrawTreasuryToken { mint, amount } => {
+                let token_vault = ctx.accounts.token_vault.as_deref().ok_or(ErrorCode::InvalidProposal)?;
+                let source = ctx.accounts.source_token_account.as_mut().ok_or(ErrorCode::InvalidProposal)?;
+                let destination = ctx
+                    .accounts
+                    .destination_token_account
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidProposal)?;
+                let mint_account = ctx.accounts.mint.as_ref().ok_or(ErrorCode::InvalidProposal)?;
+                let token_program = ctx.accounts.token_program.as_ref().ok_or(ErrorCode::InvalidProposal)?;
+                require_keys_eq!(token_vault.mint, mint, ErrorCode::WrongCurrency);
+                require_keys_eq!(mint_account.key(), mint, ErrorCode::WrongCurrency);
+
+                if matches!(proposal.action, ProposalAction::AdminWithdrawToken { .. }) {
+                    require_keys_eq!(source.key(), token_vault.vault_token_account, ErrorCode::InvalidProposal);
+                    token_transfer(
+                        source,
+                        destination,
+                        &ctx.accounts.vault,
+                        mint_account,
+                        token_program,
+                        amount,
+                        Some(&[b"vault", &[config.vault_bump]]),
+                    )?;
+                    source.reload()?;
+                    require_token_solvent(source, token_vault)?;
+                } else {
+                    require_keys_eq!(source.key(), token_vault.treasury_token_account, ErrorCode::InvalidProposal);
+                    token_transfer(
+                        source,
+                        destination,
+                        &ctx.accounts.treasury,
+                        mint_account,
+                        token_program,
+                        amount,
+                        Some(&[b"treasury", &[config.treasury_bump]]),
+                    )?;
+                }
+            }
+        }
+
+        proposal.executed = true;
+        Ok(())
+    }
+
+    // =================================================
+    // 36. INIT TOKEN VAULT (per mint)
+    // =================================================
+    pub fn init_token_vault(ctx: Context<InitTokenVault>) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        require_supported_mint(&ctx.accounts.mint.to_account_info())?;
+
+        let token_vault = &mut ctx.accounts.token_vault;
+        token_vault.mint = ctx.accounts.mint.key();
+        token_vault.decimals = ctx.accounts.mint.decimals;
+        token_vault.token_program = ctx.accounts.token_program.key();
+        token_vault.vault_token_account = ctx.accounts.vault_token_account.key();
+        token_vault.treasury_token_account = ctx.accounts.treasury_token_account.key();
+        token_vault.total_user_balances = 0;
+        token_vault.total_liability = 0;
+        token_vault.pending_withdrawals = 0;
+        token_vault.pending_tax = 0;
+        token_vault.bump = ctx.bumps.token_vault;
+        Ok(())
+    }
+
+    // =================================================
+    // 37. CREATE TOKEN USER (keyed by wallet + mint)
+    // =================================================
+    pub fn create_token_user(ctx: Context<CreateTokenUser>) -> Result<()> {
+        let user = &mut ctx.accounts.user;
+        user.balance = 0;
+        user.open_bets = 0;
+        user.wallet = ctx.accounts.user_wallet.key();
+        user.mint = ctx.accounts.mint.key();
+        Ok(())
+    }
+
+    // =================================================
+    // 38. DEPOSIT TOKEN
+    // =================================================
+    pub fn deposit_token(ctx: Context<DepositToken>, amount: u64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_DEPOSITS)?;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let before = ctx.accounts.vault_token_account.amount;
+        token_transfer(
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.user_wallet,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            amount,
+            None,
+        )?;
+
+        // Transfer-fee mints withhold part of `amount`: credit what actually arrived
+        ctx.accounts.vault_token_account.reload()?;
+        let received = checked_sub(ctx.accounts.vault_token_account.amount, before)?;
+        require!(received > 0, ErrorCode::InvalidAmount);
+
+        let user = &mut ctx.accounts.user;
+        let token_vault = &mut ctx.accounts.token_vault;
+        user.balance = checked_add(user.balance, received)?;
+        token_vault.total_user_balances = checked_add(token_vault.total_user_balances, received)?;
+        Ok(())
+    }
+
+    // =================================================
+    // 39. WITHDRAW TOKEN
+    // =================================================
+    pub fn withdraw_token(ctx: Context<WithdrawToken>, amount: u64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_WITHDRAWALS)?;
+        let user = &mut ctx.accounts.user;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(user.balance >= amount, ErrorCode::InsufficientBalance);
+
+        token_transfer(
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            amount,
+            Some(&[b"vault", &[ctx.accounts.config.vault_bump]]),
+        )?;
+
+        user.balance = checked_sub(user.balance, amount)?;
+        let token_vault = &mut ctx.accounts.token_vault;
+        token_vault.total_user_balances = checked_sub(token_vault.total_user_balances, amount)?;
+        Ok(())
+    }
+
+    // =================================================
+    // 40. PLACE TOKEN BET (from internal token balance)
+    // =================================================
+    pub fn place_token_bet(
+        ctx: Context<PlaceTokenBet>,
+        amount: u64,
+        auto_cashout_multiplier: Option<u64>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require_not_paused(config, PAUSE_BETS)?;
+        require_keys_eq!(ctx.accounts.game_state.mint, ctx.accounts.mint.key(), ErrorCode::WrongCurrency);
+
+        let (tax, max_payout) = open_bet(
+            &mut ctx.accounts.bet,
+            &mut ctx.accounts.user_balance,
+            &mut ctx.accounts.game_state,
+            config,
+            amount,
+            auto_cashout_multiplier,
+        )?;
+
+        // ---- Ledger & solvency (tax waits in the vault, see collect_game_token_tax) ----
+        let token_vault = &mut ctx.accounts.token_vault;
+        token_vault.total_user_balances = checked_sub(token_vault.total_user_balances, amount)?;
+        token_vault.pending_tax = checked_add(token_vault.pending_tax, tax)?;
+        token_vault.total_liability = checked_add(token_vault.total_liability, max_payout)?;
+        require_token_solvent(&ctx.accounts.vault_token_account, token_vault)?;
+
+        Ok(())
+    }
+
+    // =================================================
+    // 41. CLAIM TOKEN PAYOUT
+    // =================================================
+    pub fn claim_token_payout(ctx: Context<ClaimTokenPayout>) -> Result<()> {
+        let token_vault: &mut TokenVault = &mut ctx.accounts.token_vault;
+        require_keys_eq!(ctx.accounts.game_state.mint, token_vault.mint, ErrorCode::WrongCurrency);
+
+        claim_bet(
+            &mut ctx.accounts.bet,
+            &mut ctx.accounts.user_balance,
+            &mut ctx.accounts.game_state,
+            &mut token_vault.total_liability,
+            &mut token_vault.total_user_balances,
+            &mut token_vault.pending_tax,
+        )
+    }
+
+    // =================================================
+    // 42. ADMIN WITHDRAW TREASURY TOKEN (collected tax)
+    // =================================================
+    pub fn admin_withdraw_treasury_token(
+        ctx: Context<AdminWithdrawTreasuryToken>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require_role(&ctx.accounts.config, Role::Treasurer, &ctx.accounts.signer.key())?;
+        require!(!ctx.accounts.config.multisig_enabled, ErrorCode::MultisigRequired);
+
+        token_transfer(
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.destination,
+            &ctx.accounts.treasury,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            amount,
+            Some(&[b"treasury", &[ctx.accounts.config.treasury_bump]]),
+        )
+    }
+
+    // =================================================
+    // 43. INCREASE BET (betting phase only)
+    // =================================================
+    pub fn increase_bet(ctx: Context<AdjustBet>, amount: u64) -> Result<()> {
+        let bet = &mut ctx.accounts.bet;
+        let user = &mut ctx.accounts.user_balance;
+        let game = &mut ctx.accounts.game_state;
+
+        require_not_paused(&ctx.accounts.config, PAUSE_BETS)?;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(bet.active, ErrorCode::BetAlreadySettled);
+        require!(game.active, ErrorCode::GameNotActive);
+        require!(
+            round_phase(game, Clock::get()?.slot) == RoundPhase::Betting,
+            ErrorCode::BettingClosed
+        );
+        require!(user.balance >= amount, ErrorCode::InsufficientBalance);
+
+        // Table limits apply to the combined gross stake
+        let gross = checked_add(checked_add(bet.amount, bet.tax_paid)?, amount)?;
+        require!(game.max_bet == 0 || gross <= game.max_bet, ErrorCode::BetAboveMaximum);
+
+        let tax = tax_on(amount, ctx.accounts.config.tax_bps)?;
+        let net = amount.checked_sub(tax).ok_or(ErrorCode::MathOverflow)?;
+
+        user.balance = checked_sub(user.balance, amount)?;
+        game.tax_collected = checked_add(game.tax_collected, tax)?;
+
+        // ---- Re-reserve the larger max payout ----
+        let target = bet_target(game, bet.auto_cashout_multiplier);
+        let old_max = payout_at(bet.amount, target)?;
+        bet.amount = checked_add(bet.amount, net)?;
+        bet.tax_paid = checked_add(bet.tax_paid, tax)?;
+        let extra = checked_sub(payout_at(bet.amount, target)?, old_max)?;
+        reserve_round_liability(game, &ctx.accounts.config, extra)?;
+
+        game.total_volume = checked_add(game.total_volume, net)?;
+        require!(
+            game.max_total_volume == 0 || game.total_volume <= game.max_total_volume,
+            ErrorCode::GameVolumeExceeded
+        );
+
+        // ---- Currency-wide ledger & solvency ----
+        let book = currency_book(
+            game,
+            &mut ctx.accounts.config,
+            ctx.accounts.token_vault.as_deref_mut(),
+        )?;
+        *book.total_user_balances = checked_sub(*book.total_user_balances, amount)?;
+        *book.pending_tax = checked_add(*book.pending_tax, tax)?;
+        *book.total_liability = checked_add(*book.total_liability, extra)?;
+
+        match ctx.accounts.token_vault.as_deref() {
+            None => require_solvent(&ctx.accounts.vault.to_account_info(), &ctx.accounts.config, 0),
+            Some(token_vault) => require_token_solvent(
+                ctx.accounts
+                    .vault_token_account
+                    .as_ref()
+                    .ok_or(ErrorCode::WrongCurrency)?,
+                token_vault,
+            ),
+        }
+    }
+
+    // =================================================
+    // 44. CANCEL BET (betting phase only, tax refunded)
+    // =================================================
+    pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
+        let bet = &ctx.accounts.bet;
+        let user = &mut ctx.accounts.user_balance;
+        let game = &mut ctx.accounts.game_state;
+
+        require!(bet.active, ErrorCode::BetAlreadySettled);
+        require!(game.active, ErrorCode::GameNotActive);
+        require!(
+            round_phase(game, Clock::get()?.slot) == RoundPhase::Betting,
+            ErrorCode::BettingClosed
+        );
+
+        let book = currency_book(
+            game,
+            &mut ctx.accounts.config,
+            ctx.accounts.token_vault.as_deref_mut(),
+        )?;
+
+        // Tax is still in the vault during betting, so the whole gross stake returns
+        *book.pending_tax = checked_sub(*book.pending_tax, bet.tax_paid)?;
+        game.tax_collected = checked_sub(game.tax_collected, bet.tax_paid)?;
+
+        let refund = checked_add(bet.amount, bet.tax_paid)?;
+        user.balance = checked_add(user.balance, refund)?;
+        user.open_bets = user.open_bets.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+        game.open_bets = game.open_bets.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+        *book.total_user_balances = checked_add(*book.total_user_balances, refund)?;
+
+        let max_payout = payout_at(bet.amount, bet_target(game, bet.auto_cashout_multiplier))?;
+        release_liability(game, book.total_liability, max_payout)?;
+        game.total_bets = checked_sub(game.total_bets, 1)?;
+        game.total_volume = checked_sub(game.total_volume, bet.amount)?;
+
+        // Bet account is closed to the payer by the `close` constraint
+        Ok(())
+    }
+
+    // =================================================
+    // 45. CLOSE BET (lost or voided bets, settled here if still open; rent back to the payer)
+    // =================================================
+    pub fn close_bet(ctx: Context<CloseBet>) -> Result<()> {
+        let bet = &ctx.accounts.bet;
+        let game = &mut ctx.accounts.game_state;
+        let voided = game.phase == RoundPhase::Voided;
+
+        require!(voided || game.revealed, ErrorCode::GameNotRevealed);
+
+        if ctx.accounts.signer.key() != bet.payer {
+            let now = Clock::get()?.unix_timestamp;
+            require!(
+                now >= game.resolved_at.saturating_add(BET_CLOSE_GRACE_SECS),
+                ErrorCode::BetCloseGracePeriod
+            );
+        }
+
+        if voided {
+            // Pay or refund into the user's balance so the round can be archived
+            let book = currency_book(
+                game,
+                &mut ctx.accounts.config,
+                ctx.accounts.token_vault.as_deref_mut(),
+            )?;
+            return claim_bet(
+                &mut ctx.accounts.bet,
+                &mut ctx.accounts.user_balance,
+                game,
+                book.total_liability,
+                book.total_user_balances,
+                book.pending_tax,
+            );
+        }
+
+        let book = liability_book(
+            game,
+            &mut ctx.accounts.config,
+            ctx.accounts.token_vault.as_deref_mut(),
+        )?;
+        let bet = &mut ctx.accounts.bet;
+        if bet.active {
+            settle_open_bet(game, book, bet)?;
+        }
+
+        if !bet.claimed {
+            // Winners go through claim_payout, which credits and closes in one step
+            let lost = bet.payout_amount == 0
+                || (bet.cashout_multiplier > 0 && !cashout_valid(game, bet.cashout_multiplier));
+            require!(lost, ErrorCode::UnclaimedPayout);
+
+            // An invalid cash-out still holds its reserved payout
+            release_liability(game, book, bet.payout_amount)?;
+
+            let user = &mut ctx.accounts.user_balance;
+            user.open_bets = user.open_bets.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+            game.open_bets = game.open_bets.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    // =================================================
+    // 46. ARCHIVE GAME (summary into index, close GameState)
+    // =================================================
+    pub fn archive_game(ctx: Context<ArchiveGame>) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::Operator, &ctx.accounts.signer.key())?;
+
+        let game = &ctx.accounts.game_state;
+        require!(
+            game.revealed || game.phase == RoundPhase::Voided,
+            ErrorCode::GameNotRevealed
+        );
+        require!(game.open_bets == 0, ErrorCode::GameHasOpenBets);
+        require!(game.liability == 0, ErrorCode::GameHasOpenBets);
+        require!(game.tax_collected == 0, ErrorCode::TaxNotCollected);
+
+        let entry = ctx
+            .accounts
+            .chunk
+            .entries
+            .iter_mut()
+            .find(|entry| entry.game_pda == game.key())
+            .ok_or(ErrorCode::InvalidChunk)?;
+        require!(!entry.archived, ErrorCode::InvalidChunk);
+
+        // crash_point is clamped to MAX_MULTIPLIER, so it fits a u16 (0 for a voided round)
+        entry.crash_point = game.crash_point as u16;
+        entry.total_bets = u32::try_from(game.total_bets).map_err(|_| ErrorCode::MathOverflow)?;
+        entry.total_volume = game.total_volume;
+        entry.crashed = game.crashed;
+        entry.archived = true;
+
+        // GameState rent goes to the operator via the `close` constraint
+        Ok(())
+    }
+
+    // =================================================
+    // 47. CLOSE USER (SOL or token balance account)
+    // =================================================
+    pub fn close_user(ctx: Context<CloseUser>) -> Result<()> {
+        let user = &ctx.accounts.user;
+        require!(user.balance == 0, ErrorCode::BalanceNotEmpty);
+        require!(user.open_bets == 0, ErrorCode::ActiveBetExists);
+
+        // `close` hands every lamport in the PDA, rent and strays alike, to the wallet
+        Ok(())
+    }
+
+    // =================================================
+    // 48. APPLY WITHDRAW TIMELOCK (queued loosening)
+    // =================================================
+    pub fn apply_withdraw_timelock(ctx: Context<SetWithdrawTimelock>) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::SuperAdmin, &ctx.accounts.signer.key())?;
+        let config = &mut ctx.accounts.config;
+        require!(config.timelock_change_at != 0, ErrorCode::NoPendingTimelockChange);
+        require!(
+            Clock::get()?.unix_timestamp >= config.timelock_change_at,
+            ErrorCode::TimelockNotExpired
+        );
+
+        config.withdraw_timelock_threshold = config.pending_timelock_threshold;
+        config.withdraw_timelock_secs = config.pending_timelock_secs;
+        config.timelock_change_at = 0;
+        Ok(())
+    }
+
+    // =================================================
+    // 49. ADMIN DEPOSIT TOKEN BOUNTY (house bankroll)
+    // =================================================
+    pub fn admin_deposit_token_bounty(ctx: Context<AdminDepositTokenBounty>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require_role(&ctx.accounts.config, Role::Treasurer, &ctx.accounts.signer.key())?;
+
+        // Not credited to any ledger: whatever lands in the vault is house funds
+        token_transfer(
+            &ctx.accounts.source,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.signer,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            amount,
+            None,
+        )
+    }
+
+    // =================================================
+    // 50. QUEUE ADMIN WITHDRAW TOKEN (always timelocked)
+    // =================================================
+    // Token amounts have no lamport threshold to compare against, so every
+    // token bankroll withdrawal waits out Config.withdraw_timelock_secs.
+    pub fn queue_admin_withdraw_token(ctx: Context<QueueAdminWithdrawToken>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require_role(&ctx.accounts.config, Role::Treasurer, &ctx.accounts.signer.key())?;
+        require!(!ctx.accounts.config.multisig_enabled, ErrorCode::MultisigRequired);
+
+        let token_vault = &mut ctx.accounts.token_vault;
+        token_vault.pending_withdrawals = checked_add(token_vault.pending_withdrawals, amount)?;
+        require_token_solvent(&ctx.accounts.vault_token_account, token_vault)?;
+
+        let config = &mut ctx.accounts.config;
+        let pending = &mut ctx.accounts.pending_withdrawal;
+        pending.id = config.withdrawal_count;
+        pending.amount = amount;
+        pending.recipient = ctx.accounts.destination.key();
+        pending.unlock_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(config.withdraw_timelock_secs)
+            .ok_or(ErrorCode::MathOverflow)?;
+        pending.mint = token_vault.mint;
+        pending.bump = ctx.bumps.pending_withdrawal;
+
+        config.withdrawal_count = checked_add(config.withdrawal_count, 1)?;
+        Ok(())
+    }
+
+    // =================================================
+    // 51. EXECUTE ADMIN WITHDRAW TOKEN (after unlock)
+    // =================================================
+    pub fn execute_admin_withdraw_token(ctx: Context<ExecuteAdminWithdrawToken>) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::Treasurer, &ctx.accounts.signer.key())?;
+        require!(!ctx.accounts.config.multisig_enabled, ErrorCode::MultisigRequired);
+
+        let pending = &ctx.accounts.pending_withdrawal;
+        require!(
+            Clock::get()?.unix_timestamp >= pending.unlock_at,
+            ErrorCode::TimelockNotExpired
+        );
+
+        token_transfer(
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.destination,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            pending.amount,
+            Some(&[b"vault", &[ctx.accounts.config.vault_bump]]),
+        )?;
+        ctx.accounts.vault_token_account.reload()?;
+
+        let token_vault = &mut ctx.accounts.token_vault;
+        token_vault.pending_withdrawals = checked_sub(token_vault.pending_withdrawals, pending.amount)?;
+        require_token_solvent(&ctx.accounts.vault_token_account, token_vault)?;
+        Ok(())
+    }
+
+    // =================================================
+    // 52. COLLECT GAME TAX (vault -> treasury, permissionless)
+    // =================================================
+    // Only after reveal: until then bets can still be cancelled or the round voided.
+    // On a voided round, once every bet is settled the tax left is the cash-outs'.
+    pub fn collect_game_tax(ctx: Context<CollectGameTax>) -> Result<()> {
+        let game = &mut ctx.accounts.game_state;
+        require_keys_eq!(game.mint, Pubkey::default(), ErrorCode::WrongCurrency);
+        require!(tax_collectable(game), ErrorCode::GameNotRevealed);
+        let tax = game.tax_collected;
+        require!(tax > 0, ErrorCode::InvalidAmount);
+
+        let config = &mut ctx.accounts.config;
+        pda_transfer(
+            &ctx.accounts.vault,
+            &ctx.accounts.treasury,
+            tax,
+            &ctx.accounts.system_program,
+            &[b"vault", &[config.vault_bump]],
+        )?;
+        config.pending_tax = checked_sub(config.pending_tax, tax)?;
+        game.tax_collected = 0;
+        Ok(())
+    }
+
+    // =================================================
+    // 53. COLLECT GAME TOKEN TAX (vault -> treasury token account)
+    // =================================================
+    pub fn collect_game_token_tax(ctx: Context<CollectGameTokenTax>) -> Result<()> {
+        let game = &mut ctx.accounts.game_state;
+        require!(tax_collectable(game), ErrorCode::GameNotRevealed);
+        let tax = game.tax_collected;
+        require!(tax > 0, ErrorCode::InvalidAmount);
+
+        token_transfer(
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            tax,
+            Some(&[b"vault", &[ctx.accounts.config.vault_bump]]),
+        )?;
+        let token_vault = &mut ctx.accounts.token_vault;
+        token_vault.pending_tax = checked_sub(token_vault.pending_tax, tax)?;
+        game.tax_collected = 0;
+        Ok(())
+    }
+
+    // =================================================
+    // 54. VOID GAME (no reveal before the timeout, permissionless)
+    // =================================================
+    // A missed reveal costs the house: recorded cash-outs are paid in full right
+    // away. Bets that never cashed out get stake and tax back through
+    // claim_payout / claim_token_payout or close_bet, but only after
+    // publish_voided_seed shows what the withheld seed was.
+    pub fn void_game(ctx: Context<VoidGame>) -> Result<()> {
+        let game = &mut ctx.accounts.game_state;
+        require!(game.active, ErrorCode::GameNotActive);
+        require!(!game.revealed, ErrorCode::AlreadyRevealed);
+
+        let clock = Clock::get()?;
+        require!(
+            clock.slot >= game.start_slot.saturating_add(REVEAL_TIMEOUT_SLOTS),
+            ErrorCode::RevealTimeoutNotReached
+        );
+
+        if game.chain_round.is_some() {
+            let chain = ctx
+                .accounts
+                .seed_chain
+                .as_mut()
+                .ok_or(ErrorCode::SeedChainNotSet)?;
+            chain.closed_rounds = checked_add(chain.closed_rounds, 1)?;
+        }
+
+        game.active = false;
+        game.phase = RoundPhase::Voided;
+        game.resolved_at = clock.unix_timestamp;
+        Ok(())
+    }
+
+    // =================================================
+    // 55. PUBLISH VOIDED SEED (opens refunds on a voided round)
+    // =================================================
+    // Anyone can then check what the withheld round would have done. For a chain
+    // round it also unblocks every later reveal, since each one checks
+    // hash(seed) against the previous link.
+    pub fn publish_voided_seed(ctx: Context<PublishVoidedSeed>, server_seed: [u8; 32]) -> Result<()> {
+        require_role(&ctx.accounts.config, Role::Resolver, &ctx.accounts.signer.key())?;
+        let game = &mut ctx.accounts.game_state;
+        require!(game.phase == RoundPhase::Voided, ErrorCode::RoundNotVoided);
+        require!(!game.seed_published, ErrorCode::AlreadyRevealed);
+
+        match game.chain_round {
+            None => require!(
+                hash(&server_seed).to_bytes() == game.seed_commitment,
+                ErrorCode::InvalidSeed
+            ),
+            Some(round) => {
+                let chain = ctx
+                    .accounts
+                    .seed_chain
+                    .as_mut()
+                    .ok_or(ErrorCode::SeedChainNotSet)?;
+                require_eq!(round, chain.revealed_rounds, ErrorCode::OutOfOrderReveal);
+                require!(
+                    hash(&server_seed).to_bytes() == chain.last_revealed,
+                    ErrorCode::InvalidSeed
+                );
+
+                game.seed_commitment = chain.last_revealed;
+                chain.last_revealed = server_seed;
+                chain.revealed_rounds = checked_add(chain.revealed_rounds, 1)?;
+            }
+        }
+
+        // A voided round has no crash point; the seed is kept for verification
+        game.server_seed = server_seed;
+        game.seed_published = true;
+        Ok(())
+    }
+}
+
+// =================================================
+// ACCOUNT STRUCTS
+// =================================================
+#[account]
+pub struct Config {
+    // Super-admin: manages roles and economic parameters
+    pub admin: Pubkey,
+    // Set by propose_admin, becomes admin once it signs accept_admin
+    pub pending_admin: Pubkey,
+    // PAUSE_* bitflags
+    pub paused: u8,
+    pub operator: Pubkey,
+    pub resolver: Pubkey,
+    pub treasurer: Pubkey,
+    pub pauser: Pubkey,
+    pub vault: Pubkey,
+    pub vault_bump: u8,
+    pub treasury: Pubkey,
+    pub treasury_bump: u8,
+    pub tax_bps: u16,
+    pub seed_chain: Pubkey,
+    pub house_edge_bps: u16,
+    // Sum of reserved max payouts for open bets plus unclaimed payouts
+    pub total_liability: u64,
+    // 0 = no cap
+    pub max_payout_per_round: u64,
+    // Internal ledger: sum of every UserBalance.balance
+    pub total_user_balances: u64,
+    // admin_withdraw total per window above this must go through queue_admin_withdraw
+    pub withdraw_timelock_threshold: u64,
+    pub withdraw_timelock_secs: i64,
+    // Sum of queued PendingWithdrawal amounts, reserved against the vault
+    pub pending_withdrawals: u64,
+    pub withdrawal_count: u64,
+    // Once set, admin_withdraw / admin_withdraw_treasury / set_tax go through Proposal
+    pub multisig_enabled: bool,
+    // Loosening timelock change, applicable from timelock_change_at (0 = none queued)
+    pub pending_timelock_threshold: u64,
+    pub pending_timelock_secs: i64,
+    pub timelock_change_at: i64,
+    // Tax held in the vault until collect_game_tax, so cancels and voids can refund it
+    pub pending_tax: u64,
+    // Immediate admin_withdraw total in the window opened at window_start,
+    // which lasts withdraw_timelock_secs
+    pub withdrawn_in_window: u64,
+    pub window_start: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    SuperAdmin, // roles, tax, house edge, payout cap
+    Operator,   // create_game
+    Resolver,   // reveal_game, resolve_game, seed chain
+    Treasurer,  // admin_withdraw, admin_deposit_bounty, admin_withdraw_treasury
+    Pauser,
+}
+
+#[account]
+pub struct UserBalance {
+    pub balance: u64,
+    // Bets placed but not yet claimed; their stakes are already out of `balance`
+    pub open_bets: u32,
+    pub wallet: Pubkey,
+    // Pubkey::default() for the SOL balance
+    pub mint: Pubkey,
+}
+
+#[account]
+pub struct GameState {
+    // Table cap: the live multiplier never grows past this value
+    pub multiplier: u64,
+    pub active: bool,
+    pub created_at: i64,
+    pub resolved_at: i64,
+    pub total_bets: u64,
+    pub total_volume: u64,
+    pub game_name: String,
+    pub admin: Pubkey,
+    pub crashed: bool,
+    // This account's own address, mirrored into Bet.game_pda
+    pub game_pda: Pubkey,
+    pub seed_commitment: [u8; 32],
+    pub server_seed: [u8; 32],
+    // SlotHashes entry mixed into the crash point, recorded at reveal
+    pub entropy_slot: u64,
+    pub crash_point: u64,
+    pub revealed: bool,
+    pub chain_round: Option<u64>,
+    pub phase: RoundPhase,
+    pub start_slot: u64,
+    // Snapshot of Config.house_edge_bps so a round's odds can't change mid-flight
+    pub house_edge_bps: u16,
+    pub liability: u64,
+    // Wager currency: Pubkey::default() = SOL, otherwise a mint with a TokenVault
+    pub mint: Pubkey,
+    // Table limits in the game's currency; max_bet / max_total_volume of 0 = no limit
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub max_total_volume: u64,
+    // Bets not yet claimed, cancelled or closed; archive_game needs this at 0
+    pub open_bets: u32,
+    // Monotonic id taken from GameIndex.total_games; the PDA seed
+    pub game_id: u64,
+    pub entropy_hash: [u8; 32],
+    // Tax from this round's bets still in the vault (see collect_game_tax)
+    pub tax_collected: u64,
+    // server_seed is public: set by reveal_game, or publish_voided_seed for a voided round
+    pub seed_published: bool,
+}
+
+// create_game argument; 0 for max_bet / max_total_volume means no limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct BetLimits {
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub max_total_volume: u64,
+}
+
+// Stored phase only moves Betting -> Crashed (or Voided); Running is derived
+// from the clock once `start_slot` has passed (see `round_phase`).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RoundPhase {
+    Betting,
+    Running,
+    Crashed,
+    // Never revealed within REVEAL_TIMEOUT_SLOTS: recorded cash-outs are paid,
+    // other bets refunded once the seed is published
+    Voided,
+}
+
+// Bustabit-style schedule: the operator pre-generates seeds s_n .. s_1 with
+// s_{i-1} = sha256(s_i) and commits to terminal_hash = sha256(s_1).
+#[account]
+pub struct SeedChain {
+    pub terminal_hash: [u8; 32],
+    pub last_revealed: [u8; 32],
+    pub length: u64,
+    pub next_round: u64,
+    // Chain position: rounds whose seed has been published (reveal or publish_voided_seed)
+    pub revealed_rounds: u64,
+    // Issued rounds that are over, revealed or voided; rotation waits for all of them
+    pub closed_rounds: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct PendingWithdrawal {
+    pub id: u64,
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub unlock_at: i64,
+    // Pubkey::default() for SOL; otherwise `recipient` is a token account of this mint
+    pub mint: Pubkey,
+    pub bump: u8,
+}
+
+// ----------------- TOKENS -----------------
+// Per-mint ledger. Tokens sit in associated token accounts owned by the
+// vault / treasury PDAs, so the same seeds sign SOL and token transfers.
+#[account]
+pub struct TokenVault {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub token_program: Pubkey,
+    pub vault_token_account: Pubkey,
+    pub treasury_token_account: Pubkey,
+    pub total_user_balances: u64,
+    pub total_liability: u64,
+    // Queued token bankroll withdrawals, reserved like Config.pending_withdrawals
+    pub pending_withdrawals: u64,
+    pub pending_tax: u64,
+    pub bump: u8,
+}
+
+// ----------------- MULTISIG -----------------
+#[account]
+pub struct Multisig {
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProposalAction {
+    AdminWithdraw { amount: u64 },
+    AdminWithdrawTreasury { amount: u64 },
+    SetTax { tax_bps: u16 },
+    AdminWithdrawToken { mint: Pubkey, amount: u64 },
+    AdminWithdrawTreasuryToken { mint: Pubkey, amount: u64 },
+}
+
+#[account]
+pub struct Proposal {
+    pub id: u64,
+    pub action: ProposalAction,
+    // Destination of withdraw actions (a token account for token actions), unused for SetTax
+    pub recipient: Pubkey,
+    // Bit i set = owners[i] approved
+    pub approvals: u16,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Bet {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub active: bool,
+    pub game_pda: Pubkey,
+    pub payout_amount: u64,
+    pub claimed: bool,
+    pub cashout_multiplier: u64,
+    pub auto_cashout_multiplier: u64,
+    // Tax taken from the gross stake; refunded by cancel_bet
+    pub tax_paid: u64,
+    // Wallet that paid the rent; receives it back when the bet is closed
+    pub payer: Pubkey,
+    // Currency of the game, copied so audits can split bets per ledger
+    pub mint: Pubkey,
+}
+
+// ----------------- INDEX -----------------
+#[account]
+pub struct GameIndex {
+    pub total_games: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct GameEntry {
+    pub game_pda: Pubkey,
+    pub created_at: i64,
+    // Summary written by archive_game once the GameState is closed
+    pub crash_point: u16,
+    pub total_bets: u32,
+    pub total_volume: u64,
+    pub crashed: bool,
+    pub archived: bool,
+}
+
+impl GameIndex {
+    pub fn current_chunk_id(&self) -> u64 {
+        self.total_games / MAX_ENTRIES_PER_CHUNK as u64
+    }
+}
+
+// PDA [b"game_index_chunk", chunk_id], allocated up front for MAX_ENTRIES_PER_CHUNK
+#[account]
+pub struct GameIndexChunk {
+    pub chunk_id: u64,
+    pub entries: Vec<GameEntry>,
+    pub bump: u8,
+}
+
+impl GameIndexChunk {
+    // discriminator + chunk_id + vec len + entries + bump; must stay under the 10 KiB CPI allocation limit
+    pub const SPACE: usize =
+        8 + 8 + 4 + MAX_ENTRIES_PER_CHUNK * (32 + 8 + 2 + 4 + 8 + 1 + 1) + 1;
+}
+
+pub const MAX_ENTRIES_PER_CHUNK: usize = 180;
+
+// 10 worst-case GameListItems (32-byte names) fit in the 1 KiB return data limit
+pub const MAX_LIST_GAMES: usize = 10;
+
+// Multipliers are expressed in hundredths (100 = 1.00x)
+pub const MIN_MULTIPLIER: u64 = 100;
+pub const MAX_MULTIPLIER: u64 = 10_000;
+
+// Config.paused bitflags
+pub const PAUSE_DEPOSITS: u8 = 1 << 0;
+pub const PAUSE_BETS: u8 = 1 << 1;
+pub const PAUSE_NEW_GAMES: u8 = 1 << 2;
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 3;
+pub const PAUSE_ALL: u8 = PAUSE_DEPOSITS | PAUSE_BETS | PAUSE_NEW_GAMES | PAUSE_WITHDRAWALS;
+
+pub const MAX_MULTISIG_OWNERS: usize = 10;
+
+pub const DEFAULT_WITHDRAW_TIMELOCK_SECS: i64 = 24 * 60 * 60;
+pub const MAX_WITHDRAW_TIMELOCK_SECS: i64 = 30 * 24 * 60 * 60;
+
+// Live multiplier compounds per slot from `start_slot` (~2x after 29 slots)
+pub const MULTIPLIER_GROWTH_BPS_PER_SLOT: u64 = 240;
+pub const MAX_BETTING_SLOTS: u64 = 1_500;
+// Unrevealed rounds can be voided this long after `start_slot`. Kept under the
+// 512 entries SlotHashes retains so a timely reveal can always find its entropy,
+// and well past the ~195 slots the curve needs to reach MAX_MULTIPLIER.
+pub const REVEAL_TIMEOUT_SLOTS: u64 = 450;
+
+// After this long past resolution anyone may close a lost bet (rent still goes to the payer)
+pub const BET_CLOSE_GRACE_SECS: i64 = 7 * 24 * 60 * 60;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AuditReport {
+    // Pubkey::default() for the SOL ledger
+    pub mint: Pubkey,
+    pub user_balances: u64,
+    pub outstanding_payouts: u64,
+    pub total_user_balances: u64,
+    pub total_liability: u64,
+    pub vault_free: u64,
+    // Balances + liability + queued withdrawals + uncollected tax
+    pub owed: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct GameListFilter {
+    pub active_only: bool,
+    pub crashed: Option<bool>,
+    pub name_prefix: Option<String>,
+}
+
+impl GameListFilter {
+    // Archived items carry no name, so they only pass an empty name_prefix
+    pub fn matches(&self, item: &GameListItem) -> bool {
+        let crashed_ok = match self.crashed {
+            Some(crashed) => item.crashed == crashed,
+            None => true,
+        };
+        let name_ok = match &self.name_prefix {
+            Some(prefix) => item.game_name.starts_with(prefix.as_str()),
+            None => true,
+        };
+        (!self.active_only || item.active) && crashed_ok && name_ok
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GameListItem {
+    pub game_id: u64,
+    pub game_pda: Pubkey,
+    pub created_at: i64,
+    // Empty / 0 for archived games: only the index summary survives
+    pub game_name: String,
+    pub multiplier: u64,
+    pub active: bool,
+    pub crashed: bool,
+    // 0 until revealed, and for voided rounds
+    pub crash_point: u16,
+    pub archived: bool,
+}
+
+// =================================================
+// CONTEXTS
+// =================================================
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + 32 + 32 + 1 + 32 + 32 + 32 + 32 + 32 + 1 + 32 + 1 + 2 + 32 + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + 8 + 1,
+        seeds = [b"game_index"],
+        bump
+    )]
+    pub game_index: Account<'info, GameIndex>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    // System-owned PDA: holds lamports only, outbound transfers are signed with its seeds
+    #[account(mut, seeds = [b"vault"], bump)]
+    pub vault: SystemAccount<'info>,
+
+    // Tax destination, same shape as the vault
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateUser<'info> {
+    #[account(
+        init,
+        payer = user_wallet,
+        space = 8 + 8 + 4 + 32 + 32,
+        seeds = [b"user_balance", user_wallet.key().as_ref()],
+        bump
+    )]
+    pub user: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub user_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"user_balance", user_wallet.key().as_ref()], bump)]
+    pub user: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub user_wallet: Signer<'info>,
+
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, seeds = [b"user_balance", user_wallet.key().as_ref()], bump)]
+    pub user: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub user_wallet: Signer<'info>,
+
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateGame<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + 8 + 1 + 8 + 8 + 8 + 8 + 4 + 32 + 32 + 1 + 32 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 1 + 8 + 2 + 8 + 32 + 8 + 8 + 8 + 4 + 8 + 32 + 8 + 1,
+        seeds = [b"game".as_ref(), &game_index.total_games.to_le_bytes()],
+        bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(mut, seeds = [b"game_index"], bump)]
+    pub game_index: Account<'info, GameIndex>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = GameIndexChunk::SPACE,
+        seeds = [b"game_index_chunk".as_ref(), &game_index.current_chunk_id().to_le_bytes()],
+        bump
+    )]
+    pub chunk: Account<'info, GameIndexChunk>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"seed_chain"], bump = seed_chain.bump)]
+    pub seed_chain: Option<Account<'info, SeedChain>>,
+
+    // Present for token-denominated games
+    pub token_vault: Option<Account<'info, TokenVault>>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBet<'info> {
+    #[account(
+        init,
+        payer = user_wallet,
+        space = 8 + 32 + 8 + 1 + 32 + 8 + 1 + 8 + 8 + 8 + 32 + 32,
+        seeds = [b"bet", user_balance.key().as_ref(), game_state.key().as_ref()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(mut, seeds = [b"user_balance", user_wallet.key().as_ref()], bump)]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub user_wallet: Signer<'info>,
+
+    #[account(seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealGame<'info> {
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    pub signer: Signer<'info>,
+
+    #[account(mut, seeds = [b"seed_chain"], bump = seed_chain.bump)]
+    pub seed_chain: Option<Account<'info, SeedChain>>,
+
+    /// CHECK: address-constrained to the SlotHashes sysvar, parsed in slot_hash_at_or_after
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveGame<'info> {
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"token_vault", game_state.mint.as_ref()], bump = token_vault.bump)]
+    pub token_vault: Option<Account<'info, TokenVault>>,
+
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPayout<'info> {
+    #[account(mut, close = signer, constraint = bet.payer == signer.key() @ ErrorCode::Unauthorized)]
+    pub bet: Account<'info, Bet>,
+
+    #[account(mut)]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct CashOut<'info> {
+    #[account(
+        mut,
+        seeds = [b"bet", user_balance.key().as_ref(), game_state.key().as_ref()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(seeds = [b"user_balance", user_wallet.key().as_ref()], bump)]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"token_vault", game_state.mint.as_ref()], bump = token_vault.bump)]
+    pub token_vault: Option<Account<'info, TokenVault>>,
+
+    pub user_wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminWithdraw<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdminDepositBounty<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTax<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetHouseEdge<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxPayoutPerRound<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminWithdrawTreasury<'info> {
+    #[account(seeds = [b"config"], bump, has_one = treasury)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"treasury"], bump = config.treasury_bump, address = config.treasury)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ListGames<'info> {
+    #[account(seeds = [b"game_index"], bump)]
+    pub game_index: Account<'info, GameIndex>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct GetGameById<'info> {
+    #[account(
+        seeds = [b"game".as_ref(), &game_id.to_le_bytes()],
+        bump
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+#[derive(Accounts)]
+pub struct InitSeedChain<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"seed_chain"],
+        bump
+    )]
+    pub seed_chain: Account<'info, SeedChain>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RotateSeedChain<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"seed_chain"], bump = seed_chain.bump)]
+    pub seed_chain: Account<'info, SeedChain>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AuditInvariants<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(seeds = [b"token_vault", token_vault.mint.as_ref()], bump = token_vault.bump)]
+    pub token_vault: Option<Account<'info, TokenVault>>,
+
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct ManageRole<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawTimelock<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueAdminWithdraw<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + 8 + 8 + 32 + 8 + 32 + 1,
+        seeds = [b"pending_withdrawal".as_ref(), &config.withdrawal_count.to_le_bytes()],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    #[account(seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAdminWithdraw<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"pending_withdrawal".as_ref(), &pending_withdrawal.id.to_le_bytes()],
+        bump = pending_withdrawal.bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut, address = pending_withdrawal.recipient)]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminWithdraw<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"pending_withdrawal".as_ref(), &pending_withdrawal.id.to_le_bytes()],
+        bump = pending_withdrawal.bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    // Required for token withdrawals
+    #[account(
+        mut,
+        seeds = [b"token_vault", pending_withdrawal.mint.as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Option<Account<'info, TokenVault>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + 4 + 32 * MAX_MULTISIG_OWNERS + 1 + 8 + 1,
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Propose<'info> {
+    #[account(mut, seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + 8 + (1 + 32 + 8) + 32 + 2 + 1 + 1,
+        seeds = [b"proposal".as_ref(), &multisig.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    #[account(seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut, seeds = [b"config"], bump, has_one = treasury)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"treasury"], bump = config.treasury_bump, address = config.treasury)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, address = proposal.recipient)]
+    pub recipient: Option<SystemAccount<'info>>,
+
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // ---- Token actions only ----
+    #[account(seeds = [b"token_vault", token_vault.mint.as_ref()], bump = token_vault.bump)]
+    pub token_vault: Option<Account<'info, TokenVault>>,
+
+    // Vault or treasury token account, matched against token_vault in the handler
+    #[account(mut)]
+    pub source_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = proposal.recipient)]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct InitTokenVault<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + 32 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"token_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Account<'info, TokenVault>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(seeds = [b"treasury"], bump = config.treasury_bump, address = config.treasury)]
+    pub treasury: SystemAccount<'info>,
+
+    // init_if_needed: anyone can pre-create an ATA, which must not block the vault
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTokenUser<'info> {
+    #[account(
+        init,
+        payer = user_wallet,
+        space = 8 + 8 + 4 + 32 + 32,
+        seeds = [b"user_balance", user_wallet.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user: Account<'info, UserBalance>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub user_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_balance", user_wallet.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub user_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user_wallet,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"token_vault", mint.key().as_ref()], bump = token_vault.bump, has_one = mint)]
+    pub token_vault: Account<'info, TokenVault>,
+
+    #[account(mut, address = token_vault.vault_token_account)]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_balance", user_wallet.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub user_wallet: Signer<'info>,
+
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"token_vault", mint.key().as_ref()], bump = token_vault.bump, has_one = mint)]
+    pub token_vault: Account<'info, TokenVault>,
+
+    #[account(mut, address = token_vault.vault_token_account)]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceTokenBet<'info> {
+    #[account(
+        init,
+        payer = user_wallet,
+        space = 8 + 32 + 8 + 1 + 32 + 8 + 1 + 8 + 8 + 8 + 32 + 32,
+        seeds = [b"bet", user_balance.key().as_ref(), game_state.key().as_ref()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        seeds = [b"user_balance", user_wallet.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub user_wallet: Signer<'info>,
+
+    #[account(mut, seeds = [b"token_vault", mint.key().as_ref()], bump = token_vault.bump, has_one = mint)]
+    pub token_vault: Account<'info, TokenVault>,
+
+    #[account(address = token_vault.vault_token_account)]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTokenPayout<'info> {
+    #[account(
+        mut,
+        seeds = [b"bet", user_balance.key().as_ref(), game_state.key().as_ref()],
+        bump,
+        close = user_wallet
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        seeds = [b"user_balance", user_wallet.key().as_ref(), game_state.mint.as_ref()],
+        bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(mut, seeds = [b"token_vault", game_state.mint.as_ref()], bump = token_vault.bump)]
+    pub token_vault: Account<'info, TokenVault>,
+
+    #[account(mut)]
     pub user_wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminWithdrawTreasuryToken<'info> {
+    #[account(seeds = [b"config"], bump, has_one = treasury)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"token_vault", mint.key().as_ref()], bump = token_vault.bump, has_one = mint)]
+    pub token_vault: Account<'info, TokenVault>,
+
+    #[account(mut, address = token_vault.treasury_token_account)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [b"treasury"], bump = config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct AdminDepositTokenBounty<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"token_vault", mint.key().as_ref()], bump = token_vault.bump, has_one = mint)]
+    pub token_vault: Account<'info, TokenVault>,
+
+    #[account(mut, address = token_vault.vault_token_account)]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = signer,
+        token::token_program = token_program
+    )]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct QueueAdminWithdrawToken<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + 8 + 8 + 32 + 8 + 32 + 1,
+        seeds = [b"pending_withdrawal".as_ref(), &config.withdrawal_count.to_le_bytes()],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    #[account(mut, seeds = [b"token_vault", mint.key().as_ref()], bump = token_vault.bump, has_one = mint)]
+    pub token_vault: Account<'info, TokenVault>,
+
+    #[account(address = token_vault.vault_token_account)]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(token::mint = mint, token::token_program = token_program)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAdminWithdrawToken<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"pending_withdrawal".as_ref(), &pending_withdrawal.id.to_le_bytes()],
+        bump = pending_withdrawal.bump,
+        constraint = pending_withdrawal.mint == mint.key() @ ErrorCode::WrongCurrency
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    #[account(mut, seeds = [b"token_vault", mint.key().as_ref()], bump = token_vault.bump, has_one = mint)]
+    pub token_vault: Account<'info, TokenVault>,
+
+    #[account(mut, address = token_vault.vault_token_account)]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = pending_withdrawal.recipient)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct VoidGame<'info> {
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+
+    // Required for seed-chain rounds
+    #[account(mut, seeds = [b"seed_chain"], bump = seed_chain.bump)]
+    pub seed_chain: Option<Account<'info, SeedChain>>,
+}
+
+#[derive(Accounts)]
+pub struct PublishVoidedSeed<'info> {
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    // Required for seed-chain rounds
+    #[account(mut, seeds = [b"seed_chain"], bump = seed_chain.bump)]
+    pub seed_chain: Option<Account<'info, SeedChain>>,
 
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CollectGameTax<'info> {
     #[account(mut)]
-    pub vault: AccountInfo<'info>,
+    pub game_state: Account<'info, GameState>,
+
+    #[account(mut, seeds = [b"config"], bump, has_one = treasury)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"treasury"], bump = config.treasury_bump, address = config.treasury)]
+    pub treasury: SystemAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(multiplier: u64, game_name: String, created_at: u32)]
-pub struct CreateGame<'info> {
+pub struct CollectGameTokenTax<'info> {
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
     #[account(
-        init,
-        payer = signer,
-        space = 8 + 8 + 1 + 8 + 8 + 8 + 8 + 4 + 32 + 32 + 1 + 32,
-        seeds = [b"game", &created_at.to_le_bytes()],
-        bump
+        mut,
+        seeds = [b"token_vault", game_state.mint.as_ref()],
+        bump = token_vault.bump,
+        has_one = mint
     )]
-    pub game_state: Account<'info, GameState>,
+    pub token_vault: Account<'info, TokenVault>,
 
-    #[account(mut, seeds = [b"game_index"], bump)]
-    pub game_index: Account<'info, GameIndex>,
+    #[account(mut, address = token_vault.vault_token_account)]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub signer: Signer<'info>,
+    #[account(mut, address = token_vault.treasury_token_account)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub system_program: Program<'info, System>,
+    #[account(seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
 
-    #[account(mut)]
-    pub chunk: UncheckedAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct PlaceBet<'info> {
+pub struct AdjustBet<'info> {
     #[account(
-        init,
-        payer = user_wallet,
-        space = 8 + 32 + 8 + 1 + 32 + 8 + 1,
+        mut,
         seeds = [b"bet", user_balance.key().as_ref(), game_state.key().as_ref()],
         bump
     )]
     pub bet: Account<'info, Bet>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [
+            b"user_balance".as_ref(),
+            user_wallet.key().as_ref(),
+            user_balance_mint_seed(&game_state),
+        ],
+        bump
+    )]
     pub user_balance: Account<'info, UserBalance>,
 
     #[account(mut)]
     pub game_state: Account<'info, GameState>,
 
-    #[account(seeds = [b"config"], bump)]
+    #[account(mut, seeds = [b"config"], bump)]
     pub config: Account<'info, Config>,
 
-    #[account(mut)]
     pub user_wallet: Signer<'info>,
 
-    #[account(mut)]
-    pub vault: AccountInfo<'info>,
+    #[account(seeds = [b"vault"], bump = config.vault_bump, address = config.vault)]
+    pub vault: SystemAccount<'info>,
 
-    #[account(mut)]
-    pub treasury: AccountInfo<'info>,
+    // Token games only
+    #[account(mut, seeds = [b"token_vault", game_state.mint.as_ref()], bump = token_vault.bump)]
+    pub token_vault: Option<Account<'info, TokenVault>>,
 
-    pub system_program: Program<'info, System>,
+    #[account(address = token_vault.as_ref().map(|vault| vault.vault_token_account).unwrap_or_default())]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 }
 
 #[derive(Accounts)]
-pub struct ResolveGame<'info> {
+pub struct CancelBet<'info> {
+    #[account(
+        mut,
+        seeds = [b"bet", user_balance.key().as_ref(), game_state.key().as_ref()],
+        bump,
+        close = user_wallet
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"user_balance".as_ref(),
+            user_wallet.key().as_ref(),
+            user_balance_mint_seed(&game_state),
+        ],
+        bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
     #[account(mut)]
     pub game_state: Account<'info, GameState>,
 
-    #[account(mut)]
-    pub vault: AccountInfo<'info>,
+    #[account(mut, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
 
     #[account(mut)]
-    pub signer: Signer<'info>,
+    pub user_wallet: Signer<'info>,
 
-    pub system_program: Program<'info, System>,
+    // Token games only
+    #[account(mut, seeds = [b"token_vault", game_state.mint.as_ref()], bump = token_vault.bump)]
+    pub token_vault: Option<Account<'info, TokenVault>>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimPayout<'info> {
-    #[account(mut)]
+pub struct CloseBet<'info> {
+    #[account(mut, close = payer, has_one = payer)]
     pub bet: Account<'info, Bet>,
 
-    #[account(mut)]
+    #[account(mut, address = bet.user)]
     pub user_balance: Account<'info, UserBalance>,
 
-    #[account(mut)]
-    pub signer: Signer<'info>,
-}
+    #[account(mut, address = bet.game_pda)]
+    pub game_state: Account<'info, GameState>,
 
-#[derive(Accounts)]
-pub struct AdminWithdraw<'info> {
+    #[account(mut, seeds = [b"config"], bump)]
     pub config: Account<'info, Config>,
 
-    #[account(mut)]
-    pub vault: AccountInfo<'info>,
-
-    #[account(mut)]
-    pub signer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct AdminDepositBounty<'info> {
-    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"token_vault", game_state.mint.as_ref()], bump = token_vault.bump)]
+    pub token_vault: Option<Account<'info, TokenVault>>,
 
     #[account(mut)]
-    pub vault: AccountInfo<'info>,
+    pub payer: SystemAccount<'info>,
 
-    #[account(mut)]
+    // The payer, or anyone once BET_CLOSE_GRACE_SECS have passed
     pub signer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SetTax<'info> {
-    #[account(mut, seeds = [b"config"], bump)]
-    pub config: Account<'info, Config>,
+pub struct ArchiveGame<'info> {
+    #[account(mut, close = signer)]
+    pub game_state: Account<'info, GameState>,
 
-    #[account(mut)]
-    pub signer: Signer<'info>,
-}
+    #[account(
+        mut,
+        seeds = [b"game_index_chunk".as_ref(), &chunk.chunk_id.to_le_bytes()],
+        bump = chunk.bump
+    )]
+    pub chunk: Account<'info, GameIndexChunk>,
 
-#[derive(Accounts)]
-pub struct AdminWithdrawTreasury<'info> {
     #[account(seeds = [b"config"], bump)]
     pub config: Account<'info, Config>,
 
-    #[account(mut)]
-    pub treasury: AccountInfo<'info>,
-
     #[account(mut)]
     pub signer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ListGames<'info> {
-    #[account(seeds = [b"game_index"], bump)]
-    pub game_index: Account<'info, GameIndex>,
-}
-
-#[derive(Accounts)]
-#[instruction(created_at: u32)]
-pub struct GetGameByTimestamp<'info> {
+pub struct CloseUser<'info> {
     #[account(
-        seeds = [b"game", &created_at.to_le_bytes()],
-        bump
+        mut,
+        close = user_wallet,
+        constraint = user.wallet == user_wallet.key() @ ErrorCode::Unauthorized
     )]
-    pub game_state: Account<'info, GameState>,
+    pub user: Account<'info, UserBalance>,
+
+    #[account(mut)]
+    pub user_wallet: Signer<'info>,
 }
 
 // =================================================
@@ -689,6 +3135,556 @@ fn system_transfer<'a>(
     transfer(cpi_ctx, amount)
 }
 
+/// Derives the crash point (in hundredths) from a revealed server seed and the
+/// hash of the first slot at or after `start_slot`. That slot hash doesn't exist
+/// when the seed is committed, so the operator can't grind seeds or time
+/// create_game towards an early bust. Anyone can recompute it from on-chain data.
+///
+/// With r uniform in [0, 1), crash = (1 - edge) / (1 - r) floored at 1.00x, so
+/// P(crash >= m) = (1 - edge) / m and every cash-out target returns exactly 1 - edge.
+pub fn derive_crash_point(
+    server_seed: &[u8; 32],
+    game: &Pubkey,
+    entropy_hash: &[u8; 32],
+    house_edge_bps: u16,
+) -> u64 {
+    let digest = hashv(&[server_seed, game.as_ref(), entropy_hash]).to_bytes();
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&digest[..8]);
+
+    // 52 uniform bits -> crash = e * (1 - edge) / (e - h)
+    let e: u128 = 1 << 52;
+    let h = (u64::from_le_bytes(word) >> 12) as u128;
+    let point = (100 * e * (10_000 - house_edge_bps as u128) / 10_000) / (e - h);
+
+    point.clamp(MIN_MULTIPLIER as u128, MAX_MULTIPLIER as u128) as u64
+}
+
+/// First (slot, hash) in the SlotHashes sysvar with slot >= `from_slot`.
+/// Entries are stored newest first; the walk must reach an older slot, otherwise
+/// the wanted entry may already have aged out and the answer isn't determined.
+fn slot_hash_at_or_after(slot_hashes: &AccountInfo, from_slot: u64) -> Result<(u64, [u8; 32])> {
+    const ENTRY_LEN: usize = 8 + 32;
+    let data = slot_hashes.try_borrow_data()?;
+    let entry = |offset: usize, len: usize| -> Result<&[u8]> {
+        Ok(data
+            .get(offset..offset + len)
+            .ok_or(ErrorCode::EntropyUnavailable)?)
+    };
+    let read_u64 = |offset: usize| -> Result<u64> {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(entry(offset, 8)?);
+        Ok(u64::from_le_bytes(word))
+    };
+
+    let len = read_u64(0)? as usize;
+    let mut found = None;
+    for i in 0..len {
+        let offset = 8 + i * ENTRY_LEN;
+        let slot = read_u64(offset)?;
+        if slot < from_slot {
+            return found.ok_or(error!(ErrorCode::EntropyUnavailable));
+        }
+        let mut slot_hash = [0u8; 32];
+        slot_hash.copy_from_slice(entry(offset + 8, 32)?);
+        found = Some((slot, slot_hash));
+    }
+    err!(ErrorCode::EntropyUnavailable)
+}
+
+/// Live multiplier (in hundredths) after `elapsed_slots` of running time.
+/// Fixed-point exponentiation by squaring so clients reproduce it bit-for-bit.
+pub fn live_multiplier(elapsed_slots: u64) -> u64 {
+    const ONE: u128 = 1_000_000_000_000;
+    let cap = ONE * MAX_MULTIPLIER as u128 / 100;
+
+    let mut base = ONE + ONE * MULTIPLIER_GROWTH_BPS_PER_SLOT as u128 / 10_000;
+    let mut acc = ONE;
+    let mut n = elapsed_slots;
+    while n > 0 {
+        if n & 1 == 1 {
+            acc = acc * base / ONE;
+            if acc >= cap {
+                return MAX_MULTIPLIER;
+            }
+        }
+        base = (base * base / ONE).min(cap);
+        n >>= 1;
+    }
+    (acc * 100 / ONE) as u64
+}
+
+/// Phase of a round at `slot`, deriving Running from the betting window.
+pub fn round_phase(game: &GameState, slot: u64) -> RoundPhase {
+    match game.phase {
+        RoundPhase::Betting if slot >= game.start_slot => RoundPhase::Running,
+        phase => phase,
+    }
+}
+
+/// Live multiplier of a round at `slot`, capped at the table's multiplier.
+pub fn game_multiplier_at(game: &GameState, slot: u64) -> u64 {
+    live_multiplier(slot.saturating_sub(game.start_slot)).min(game.multiplier)
+}
+
+/// A cash-out stands only if it happened strictly below the crash point,
+/// or the round ran all the way to the table cap.
+pub fn cashout_valid(game: &GameState, cashout_multiplier: u64) -> bool {
+    cashout_multiplier < game.crash_point || !game.crashed
+}
+
+/// Settles a bet still open at reveal: it rode to its auto cash-out target (or
+/// the table cap) and wins only if the round got there. The unpaid part of its
+/// reservation is released; the payout stays reserved until claimed.
+fn settle_open_bet(game: &mut GameState, total_liability: &mut u64, bet: &mut Bet) -> Result<()> {
+    let target = bet_target(game, bet.auto_cashout_multiplier);
+    let max_payout = payout_at(bet.amount, target)?;
+    let payout = if cashout_valid(game, target) { max_payout } else { 0 };
+    release_liability(game, total_liability, max_payout - payout)?;
+
+    bet.active = false;
+    bet.payout_amount = payout;
+    Ok(())
+}
+
+fn tax_collectable(game: &GameState) -> bool {
+    game.revealed || (game.phase == RoundPhase::Voided && game.open_bets == 0)
+}
+
+/// Multiplier a still-open bet settles at if the round reaches it.
+fn bet_target(game: &GameState, auto_cashout_multiplier: u64) -> u64 {
+    match auto_cashout_multiplier {
+        0 => game.multiplier,
+        auto => auto.min(game.multiplier),
+    }
+}
+
+fn payout_at(amount: u64, multiplier: u64) -> Result<u64> {
+    amount
+        .checked_mul(multiplier)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(100)
+        .ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// Liability counter for a game's currency: Config for SOL, the mint's TokenVault otherwise.
+fn liability_book<'a>(
+    game: &GameState,
+    config: &'a mut Config,
+    token_vault: Option<&'a mut TokenVault>,
+) -> Result<&'a mut u64> {
+    if game.mint == Pubkey::default() {
+        return Ok(&mut config.total_liability);
+    }
+    let token_vault = token_vault.ok_or(ErrorCode::WrongCurrency)?;
+    require_keys_eq!(token_vault.mint, game.mint, ErrorCode::WrongCurrency);
+    Ok(&mut token_vault.total_liability)
+}
+
+/// Currency-wide counters a bet moves: Config for SOL, the mint's TokenVault otherwise.
+struct CurrencyBook<'a> {
+    total_user_balances: &'a mut u64,
+    total_liability: &'a mut u64,
+    pending_tax: &'a mut u64,
+}
+
+fn currency_book<'a>(
+    game: &GameState,
+    config: &'a mut Config,
+    token_vault: Option<&'a mut TokenVault>,
+) -> Result<CurrencyBook<'a>> {
+    if game.mint == Pubkey::default() {
+        return Ok(CurrencyBook {
+            total_user_balances: &mut config.total_user_balances,
+            total_liability: &mut config.total_liability,
+            pending_tax: &mut config.pending_tax,
+        });
+    }
+    let token_vault = token_vault.ok_or(ErrorCode::WrongCurrency)?;
+    require_keys_eq!(token_vault.mint, game.mint, ErrorCode::WrongCurrency);
+    Ok(CurrencyBook {
+        total_user_balances: &mut token_vault.total_user_balances,
+        total_liability: &mut token_vault.total_liability,
+        pending_tax: &mut token_vault.pending_tax,
+    })
+}
+
+/// Trailing UserBalance seed for a game's currency: none for SOL, the mint
+/// otherwise. An empty seed adds nothing to the derivation, so SOL balances
+/// keep their [b"user_balance", wallet] address.
+fn user_balance_mint_seed(game: &GameState) -> &[u8] {
+    if game.mint == Pubkey::default() {
+        &[]
+    } else {
+        game.mint.as_ref()
+    }
+}
+
+fn release_liability(game: &mut GameState, total_liability: &mut u64, amount: u64) -> Result<()> {
+    game.liability = checked_sub(game.liability, amount)?;
+    *total_liability = checked_sub(*total_liability, amount)?;
+    Ok(())
+}
+
+/// Bookkeeping shared by place_bet and place_token_bet: debits the user's
+/// ledger balance, initialises the bet and reserves its max payout on the game.
+/// Returns (tax, max_payout); moving the tax and the currency-wide book is up to the caller.
+fn open_bet(
+    bet: &mut Account<Bet>,
+    user: &mut Account<UserBalance>,
+    game: &mut Account<GameState>,
+    config: &Config,
+    amount: u64,
+    auto_cashout_multiplier: Option<u64>,
+) -> Result<(u64, u64)> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    if let Some(target) = auto_cashout_multiplier {
+        require!(
+            (MIN_MULTIPLIER..=MAX_MULTIPLIER).contains(&target),
+            ErrorCode::InvalidMultiplier
+        );
+    }
+
+    require!(game.active, ErrorCode::GameNotActive);
+    require!(
+        round_phase(game, Clock::get()?.slot) == RoundPhase::Betting,
+        ErrorCode::BettingClosed
+    );
+    require!(amount >= game.min_bet, ErrorCode::BetBelowMinimum);
+    require!(game.max_bet == 0 || amount <= game.max_bet, ErrorCode::BetAboveMaximum);
+    require!(user.balance >= amount, ErrorCode::InsufficientBalance);
+
+    // ---- TAX ----
+    let tax = tax_on(amount, config.tax_bps)?;
+    let bet_after_tax = amount.checked_sub(tax).ok_or(ErrorCode::MathOverflow)?;
+
+    // ---- Update user & bet (net amount) ----
+    user.balance = checked_sub(user.balance, amount)?;
+    user.open_bets = user.open_bets.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    game.open_bets = game.open_bets.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    bet.user = user.key();
+    bet.amount = bet_after_tax;
+    bet.active = true;
+    bet.game_pda = game.game_pda;
+    bet.payout_amount = 0;
+    bet.claimed = false;
+    bet.cashout_multiplier = 0;
+    bet.auto_cashout_multiplier = auto_cashout_multiplier.unwrap_or(0);
+    bet.tax_paid = tax;
+    bet.payer = user.wallet;
+    bet.mint = game.mint;
+
+    game.total_bets = checked_add(game.total_bets, 1)?;
+    game.tax_collected = checked_add(game.tax_collected, tax)?;
+    game.total_volume = checked_add(game.total_volume, bet_after_tax)?;
+    require!(
+        game.max_total_volume == 0 || game.total_volume <= game.max_total_volume,
+        ErrorCode::GameVolumeExceeded
+    );
+
+    // ---- Reserve the bet's maximum payout ----
+    let max_payout = payout_at(bet_after_tax, bet_target(game, bet.auto_cashout_multiplier))?;
+    reserve_round_liability(game, config, max_payout)?;
+
+    Ok((tax, max_payout))
+}
+
+fn tax_on(amount: u64, tax_bps: u16) -> Result<u64> {
+    Ok(amount
+        .checked_mul(tax_bps as u64)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(ErrorCode::MathOverflow)?)
+}
+
+fn reserve_round_liability(game: &mut GameState, config: &Config, amount: u64) -> Result<()> {
+    game.liability = checked_add(game.liability, amount)?;
+    require!(
+        config.max_payout_per_round == 0 || game.liability <= config.max_payout_per_round,
+        ErrorCode::RoundPayoutCapExceeded
+    );
+    Ok(())
+}
+
+/// Shared by claim_payout, claim_token_payout and close_bet: moves a settled
+/// bet's payout out of the liability book and into the user's balance. Lost
+/// bets settle with a zero payout so they still close out the user's open-bet
+/// count. On a voided round cash-outs are paid and other bets refunded.
+fn claim_bet(
+    bet: &mut Account<Bet>,
+    user: &mut Account<UserBalance>,
+    game: &mut GameState,
+    total_liability: &mut u64,
+    total_user_balances: &mut u64,
+    pending_tax: &mut u64,
+) -> Result<()> {
+    require!(!bet.claimed, ErrorCode::AlreadyClaimed);
+    require_keys_eq!(bet.user, user.key(), ErrorCode::Unauthorized);
+    require_keys_eq!(bet.game_pda, game.game_pda, ErrorCode::InvalidPda);
+
+    if game.phase == RoundPhase::Voided {
+        if bet.cashout_multiplier > 0 {
+            // Withholding the reveal can't turn a recorded cash-out into a loss
+            release_liability(game, total_liability, bet.payout_amount)?;
+        } else {
+            require!(game.seed_published, ErrorCode::SeedNotPublished);
+            let max_payout = payout_at(bet.amount, bet_target(game, bet.auto_cashout_multiplier))?;
+            release_liability(game, total_liability, max_payout)?;
+            *pending_tax = checked_sub(*pending_tax, bet.tax_paid)?;
+            game.tax_collected = checked_sub(game.tax_collected, bet.tax_paid)?;
+
+            bet.active = false;
+            bet.payout_amount = checked_add(bet.amount, bet.tax_paid)?;
+        }
+    } else {
+        // Cash-outs are only honoured once the crash point is public
+        require!(game.revealed, ErrorCode::GameNotRevealed);
+        if bet.active {
+            // resolve_game may have skipped it; the outcome is public either way
+            settle_open_bet(game, total_liability, bet)?;
+        }
+        if bet.cashout_multiplier > 0 && !cashout_valid(game, bet.cashout_multiplier) {
+            // Cashed out at or after the crash: the bet is lost
+            release_liability(game, total_liability, bet.payout_amount)?;
+            bet.payout_amount = 0;
+        }
+
+        // Payout leaves the liability book
+        release_liability(game, total_liability, bet.payout_amount)?;
+    }
+
+    user.balance = checked_add(user.balance, bet.payout_amount)?;
+    *total_user_balances = checked_add(*total_user_balances, bet.payout_amount)?;
+    user.open_bets = user.open_bets.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+    game.open_bets = game.open_bets.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+    bet.claimed = true;
+
+    Ok(())
+}
+
+/// Vault lamports above rent exemption must cover every user balance, every
+/// outstanding liability, every queued admin withdrawal and uncollected tax,
+/// even after `outflow` more lamports leave the vault.
+fn require_solvent(vault: &AccountInfo, config: &Config, outflow: u64) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(vault.data_len());
+    let free = vault.lamports().saturating_sub(rent).saturating_sub(outflow);
+    require!(free >= sol_owed(config)?, ErrorCode::HouseExposureExceeded);
+    Ok(())
+}
+
+/// Everything a vault must back; shared by the solvency checks and audit_invariants.
+fn ledger_owed(
+    total_user_balances: u64,
+    total_liability: u64,
+    pending_withdrawals: u64,
+    pending_tax: u64,
+) -> Result<u64> {
+    let owed = checked_add(total_user_balances, total_liability)?;
+    let owed = checked_add(owed, pending_withdrawals)?;
+    checked_add(owed, pending_tax)
+}
+
+fn sol_owed(config: &Config) -> Result<u64> {
+    ledger_owed(
+        config.total_user_balances,
+        config.total_liability,
+        config.pending_withdrawals,
+        config.pending_tax,
+    )
+}
+
+fn token_owed(token_vault: &TokenVault) -> Result<u64> {
+    ledger_owed(
+        token_vault.total_user_balances,
+        token_vault.total_liability,
+        token_vault.pending_withdrawals,
+        token_vault.pending_tax,
+    )
+}
+
+/// Outbound transfer from a system-owned PDA (vault / treasury), signed with its seeds.
+fn pda_transfer<'a>(
+    from: &SystemAccount<'a>,
+    to: &impl ToAccountInfo<'a>,
+    amount: u64,
+    system_program: &Program<'a, System>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: from.to_account_info(),
+        to: to.to_account_info(),
+    };
+    let signer_seeds = &[seeds];
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    transfer(cpi_ctx, amount)
+}
+
+/// Tops a lamport-only PDA up to rent exemption so it can receive small transfers.
+fn fund_rent_exempt<'a>(
+    payer: &Signer<'a>,
+    pda: &SystemAccount<'a>,
+    system_program: &Program<'a, System>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(0);
+    let lamports = pda.lamports();
+    if lamports < rent {
+        system_transfer(payer, pda, rent - lamports, system_program)?;
+    }
+    Ok(())
+}
+
+fn role_holder(config: &Config, role: Role) -> Pubkey {
+    match role {
+        Role::SuperAdmin => config.admin,
+        Role::Operator => config.operator,
+        Role::Resolver => config.resolver,
+        Role::Treasurer => config.treasurer,
+        Role::Pauser => config.pauser,
+    }
+}
+
+fn set_role_holder(config: &mut Config, role: Role, holder: Pubkey) {
+    match role {
+        Role::SuperAdmin => config.admin = holder,
+        Role::Operator => config.operator = holder,
+        Role::Resolver => config.resolver = holder,
+        Role::Treasurer => config.treasurer = holder,
+        Role::Pauser => config.pauser = holder,
+    }
+}
+
+fn require_role(config: &Config, role: Role, signer: &Pubkey) -> Result<()> {
+    let holder = role_holder(config, role);
+    // A revoked role (default key) can never be satisfied
+    require_keys_neq!(holder, Pubkey::default(), ErrorCode::Unauthorized);
+    require_keys_eq!(*signer, holder, ErrorCode::Unauthorized);
+    Ok(())
+}
+
+fn multisig_owner_index(multisig: &Multisig, signer: &Pubkey) -> Result<usize> {
+    multisig
+        .owners
+        .iter()
+        .position(|owner| owner == signer)
+        .ok_or(ErrorCode::Unauthorized.into())
+}
+
+/// Outbound transfer from the treasury PDA, keeping it rent exempt.
+fn treasury_withdraw<'a>(
+    treasury: &SystemAccount<'a>,
+    to: &impl ToAccountInfo<'a>,
+    amount: u64,
+    system_program: &Program<'a, System>,
+    treasury_bump: u8,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    let rent = Rent::get()?.minimum_balance(treasury.to_account_info().data_len());
+    require!(
+        treasury.lamports().saturating_sub(rent) >= amount,
+        ErrorCode::InsufficientBalance
+    );
+
+    pda_transfer(
+        treasury,
+        to,
+        amount,
+        system_program,
+        &[b"treasury", &[treasury_bump]],
+    )
+}
+
+fn require_not_paused(config: &Config, flag: u8) -> Result<()> {
+    require!(config.paused & flag == 0, ErrorCode::Paused);
+    Ok(())
+}
+
+/// transfer_checked through token_interface; `signer_seeds` when a PDA owns `from`.
+fn token_transfer<'info>(
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: &impl ToAccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+    signer_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: from.to_account_info(),
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+    match signer_seeds {
+        Some(seeds) => {
+            let signer = &[seeds];
+            let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)
+        }
+        None => {
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)
+        }
+    }
+}
+
+/// Token-2022 mints are accepted except for extensions that need extra
+/// accounts on every transfer (transfer hooks), block transfers outright, or
+/// let a permanent delegate move tokens straight out of the vault.
+fn require_supported_mint(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    for extension in state.get_extension_types()? {
+        require!(
+            !matches!(
+                extension,
+                ExtensionType::TransferHook
+                    | ExtensionType::NonTransferable
+                    | ExtensionType::PermanentDelegate
+            ),
+            ErrorCode::UnsupportedMint
+        );
+    }
+    Ok(())
+}
+
+/// Deserializes a chunk passed in remaining_accounts, checking owner, id and PDA.
+fn load_index_chunk(
+    chunk_acc: &AccountInfo,
+    chunk_id: u64,
+    program_id: &Pubkey,
+) -> Result<GameIndexChunk> {
+    require_keys_eq!(*chunk_acc.owner, *program_id, ErrorCode::InvalidChunk);
+    let chunk = GameIndexChunk::try_deserialize(&mut &chunk_acc.data.borrow()[..])?;
+    require_eq!(chunk.chunk_id, chunk_id, ErrorCode::InvalidChunk);
+
+    let expected = Pubkey::create_program_address(
+        &[b"game_index_chunk", &chunk_id.to_le_bytes(), &[chunk.bump]],
+        program_id,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidChunk))?;
+    require_keys_eq!(chunk_acc.key(), expected, ErrorCode::InvalidChunk);
+    Ok(chunk)
+}
+
+fn require_token_solvent(
+    vault_token_account: &InterfaceAccount<TokenAccount>,
+    token_vault: &TokenVault,
+) -> Result<()> {
+    require!(
+        vault_token_account.amount >= token_owed(token_vault)?,
+        ErrorCode::HouseExposureExceeded
+    );
+    Ok(())
+}
+
 fn checked_add(a: u64, b: u64) -> Result<u64> {
     a.checked_add(b).ok_or(ErrorCode::MathOverflow.into())
 }
@@ -732,4 +3728,213 @@ pub enum ErrorCode {
     TaxTooHigh,
     #[msg("User already has an active bet")]
     ActiveBetExists,
-}
\ No newline at end of file
+    #[msg("Server seed does not match commitment")]
+    InvalidSeed,
+    #[msg("Game already revealed")]
+    AlreadyRevealed,
+    #[msg("Game not revealed yet")]
+    GameNotRevealed,
+    #[msg("Seed chain not configured")]
+    SeedChainNotSet,
+    #[msg("Seed chain exhausted")]
+    SeedChainExhausted,
+    #[msg("Seed chain still has unrevealed rounds")]
+    SeedChainInUse,
+    #[msg("Rounds must be revealed in order")]
+    OutOfOrderReveal,
+    #[msg("Betting window too long")]
+    InvalidBettingWindow,
+    #[msg("Betting window is closed")]
+    BettingClosed,
+    #[msg("Round is not running")]
+    RoundNotRunning,
+    #[msg("Round has not reached its crash point yet")]
+    RoundStillRunning,
+    #[msg("Bet already settled")]
+    BetAlreadySettled,
+    #[msg("House edge cannot exceed 5%")]
+    HouseEdgeTooHigh,
+    #[msg("Vault cannot cover outstanding payouts")]
+    HouseExposureExceeded,
+    #[msg("Round payout cap exceeded")]
+    RoundPayoutCapExceeded,
+    #[msg("Account cannot be audited")]
+    InvalidAuditAccount,
+    #[msg("Ledger invariant violated")]
+    InvariantViolated,
+    #[msg("Invalid role")]
+    InvalidRole,
+    #[msg("No admin transfer pending")]
+    NoPendingAdmin,
+    #[msg("Paused")]
+    Paused,
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    #[msg("Amount above threshold: queue a timelocked withdrawal")]
+    TimelockRequired,
+    #[msg("Timelock has not expired")]
+    TimelockNotExpired,
+    #[msg("Invalid timelock delay")]
+    InvalidTimelock,
+    #[msg("Multisig mode is enabled: use a proposal")]
+    MultisigRequired,
+    #[msg("Invalid multisig owners or threshold")]
+    InvalidMultisig,
+    #[msg("Invalid proposal")]
+    InvalidProposal,
+    #[msg("Proposal already executed")]
+    ProposalExecuted,
+    #[msg("Not enough approvals")]
+    NotEnoughApprovals,
+    #[msg("Wrong currency for this game")]
+    WrongCurrency,
+    #[msg("Mint extension not supported")]
+    UnsupportedMint,
+    #[msg("Bet below table minimum")]
+    BetBelowMinimum,
+    #[msg("Bet above table maximum")]
+    BetAboveMaximum,
+    #[msg("Game volume limit reached")]
+    GameVolumeExceeded,
+    #[msg("Invalid bet limits")]
+    InvalidBetLimits,
+    #[msg("Bet can only be closed by its payer during the grace period")]
+    BetCloseGracePeriod,
+    #[msg("Bet has an unclaimed payout")]
+    UnclaimedPayout,
+    #[msg("Game still has open bets")]
+    GameHasOpenBets,
+    #[msg("Balance must be zero")]
+    BalanceNotEmpty,
+    #[msg("Account missing from remaining_accounts")]
+    MissingListAccount,
+    #[msg("List limit too high")]
+    ListLimitTooHigh,
+    #[msg("Slot hash for this round is not available")]
+    EntropyUnavailable,
+    #[msg("No timelock change queued")]
+    NoPendingTimelockChange,
+    #[msg("Game tax has not been collected")]
+    TaxNotCollected,
+    #[msg("Round can't be voided before the reveal timeout")]
+    RevealTimeoutNotReached,
+    #[msg("Round has not been voided")]
+    RoundNotVoided,
+    #[msg("Server seed of the voided round has not been published")]
+    SeedNotPublished,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(crash_point: u64, crashed: bool) -> GameState {
+        GameState {
+            multiplier: MAX_MULTIPLIER,
+            active: false,
+            created_at: 0,
+            resolved_at: 0,
+            total_bets: 0,
+            total_volume: 0,
+            game_name: String::new(),
+            admin: Pubkey::default(),
+            crashed,
+            game_pda: Pubkey::default(),
+            seed_commitment: [0; 32],
+            server_seed: [0; 32],
+            entropy_slot: 0,
+            crash_point,
+            revealed: true,
+            chain_round: None,
+            phase: RoundPhase::Crashed,
+            start_slot: 0,
+            house_edge_bps: 0,
+            liability: 0,
+            mint: Pubkey::default(),
+            min_bet: 0,
+            max_bet: 0,
+            max_total_volume: 0,
+            open_bets: 0,
+            game_id: 0,
+            entropy_hash: [0; 32],
+            tax_collected: 0,
+            seed_published: true,
+        }
+    }
+
+    // SlotHashes layout: u64 count, then (slot, hash) pairs newest first
+    fn slot_hashes_data(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for &slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[slot as u8; 32]);
+        }
+        data
+    }
+
+    fn lookup(mut data: Vec<u8>, from_slot: u64) -> Result<(u64, [u8; 32])> {
+        let key = anchor_lang::solana_program::sysvar::slot_hashes::ID;
+        let owner = anchor_lang::solana_program::sysvar::ID;
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        slot_hash_at_or_after(&info, from_slot)
+    }
+
+    #[test]
+    fn live_multiplier_vectors() {
+        assert_eq!(live_multiplier(0), 100);
+        assert_eq!(live_multiplier(1), 102);
+        assert_eq!(live_multiplier(29), 198);
+        assert_eq!(live_multiplier(194), 9_958);
+        assert_eq!(live_multiplier(195), MAX_MULTIPLIER);
+        assert_eq!(live_multiplier(500), MAX_MULTIPLIER);
+        assert_eq!(live_multiplier(u64::MAX), MAX_MULTIPLIER);
+    }
+
+    #[test]
+    fn derive_crash_point_vectors() {
+        let game = Pubkey::new_from_array([3; 32]);
+        let entropy = [9; 32];
+        assert_eq!(derive_crash_point(&[7; 32], &game, &entropy, 0), 119);
+        assert_eq!(derive_crash_point(&[7; 32], &game, &entropy, 100), 118);
+        assert_eq!(derive_crash_point(&[7; 32], &game, &entropy, 500), 113);
+
+        // The edge pushes 1.01x below 1.00x, which floors
+        assert_eq!(derive_crash_point(&[27; 32], &game, &entropy, 0), 101);
+        assert_eq!(derive_crash_point(&[27; 32], &game, &entropy, 100), MIN_MULTIPLIER);
+        // Far tail clamps to the cap
+        assert_eq!(derive_crash_point(&[98; 32], &game, &entropy, 100), MAX_MULTIPLIER);
+    }
+
+    #[test]
+    fn slot_hash_skips_to_next_slot_when_start_slot_was_skipped() {
+        // Slot 102 produced no block
+        let data = slot_hashes_data(&[105, 103, 101, 100]);
+        assert_eq!(lookup(data.clone(), 102).unwrap(), (103, [103; 32]));
+        assert_eq!(lookup(data.clone(), 103).unwrap(), (103, [103; 32]));
+        assert_eq!(lookup(data, 101).unwrap(), (101, [101; 32]));
+    }
+
+    #[test]
+    fn slot_hash_needs_an_older_entry_to_be_determined() {
+        let unavailable: Error = ErrorCode::EntropyUnavailable.into();
+        // Nothing at or after the slot yet
+        assert_eq!(lookup(slot_hashes_data(&[105, 103]), 106).unwrap_err(), unavailable);
+        // Oldest entry is already past the slot: the exact one may have aged out
+        assert_eq!(lookup(slot_hashes_data(&[105, 103]), 102).unwrap_err(), unavailable);
+        assert_eq!(lookup(slot_hashes_data(&[]), 102).unwrap_err(), unavailable);
+        // Count claims more entries than the buffer holds
+        let mut data = slot_hashes_data(&[105, 103, 100]);
+        data.truncate(8 + 2 * 40 + 4);
+        assert_eq!(lookup(data, 102).unwrap_err(), unavailable);
+    }
+
+    #[test]
+    fn cashout_valid_vectors() {
+        assert!(cashout_valid(&game(250, true), 249));
+        assert!(!cashout_valid(&game(250, true), 250));
+        assert!(!cashout_valid(&game(250, true), 300));
+        // Ran to the table cap without crashing
+        assert!(cashout_valid(&game(MAX_MULTIPLIER, false), MAX_MULTIPLIER));
+    }
+}
@@ -0,0 +1,59 @@
+// Shared harness: runs the program natively under solana-program-test.
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_program_test::BanksClient;
+use solana_sdk::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+// Anchor's entry ties the account slice and AccountInfo lifetimes together
+pub fn process<'a>(program_id: &Pubkey, accounts: &[AccountInfo<'a>], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    crash3::entry(program_id, accounts, data)
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crash3::ID).0
+}
+
+pub async fn send(
+    banks: &mut BanksClient,
+    payer: &Keypair,
+    blockhash: Hash,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), solana_program_test::BanksClientError> {
+    let mut all: Vec<&Keypair> = vec![payer];
+    all.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all, blockhash);
+    banks.process_transaction(tx).await
+}
+
+pub async fn initialize(banks: &mut BanksClient, payer: &Keypair, blockhash: Hash) {
+    let ix = Instruction {
+        program_id: crash3::ID,
+        accounts: crash3::accounts::Initialize {
+            config: pda(&[b"config"]),
+            game_index: pda(&[b"game_index"]),
+            signer: payer.pubkey(),
+            vault: pda(&[b"vault"]),
+            treasury: pda(&[b"treasury"]),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: crash3::instruction::Initialize {
+            admin: payer.pubkey(),
+            tax_bps: 0,
+            house_edge_bps: 100,
+        }
+        .data(),
+    };
+    send(banks, payer, blockhash, &[ix], &[]).await.unwrap();
+}
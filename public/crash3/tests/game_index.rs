@@ -0,0 +1,198 @@
+// list_games over an index that spans two chunks, including an archived game.
+
+mod common;
+
+use anchor_lang::{AnchorDeserialize, InstructionData, ToAccountMetas};
+use common::{initialize, pda, process, send};
+use crash3::{BetLimits, GameListFilter, GameListItem, MAX_ENTRIES_PER_CHUNK};
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+// Spills a few games into the second chunk
+const GAMES: u64 = MAX_ENTRIES_PER_CHUNK as u64 + 5;
+const ARCHIVED: u64 = MAX_ENTRIES_PER_CHUNK as u64 + 1;
+
+fn game_pda(game_id: u64) -> Pubkey {
+    pda(&[b"game", &game_id.to_le_bytes()])
+}
+
+fn chunk_pda(chunk_id: u64) -> Pubkey {
+    pda(&[b"game_index_chunk", &chunk_id.to_le_bytes()])
+}
+
+fn create_game_ix(payer: &Pubkey, game_id: u64) -> Instruction {
+    Instruction {
+        program_id: crash3::ID,
+        accounts: crash3::accounts::CreateGame {
+            game_state: game_pda(game_id),
+            game_index: pda(&[b"game_index"]),
+            signer: *payer,
+            system_program: system_program::ID,
+            chunk: chunk_pda(game_id / MAX_ENTRIES_PER_CHUNK as u64),
+            config: pda(&[b"config"]),
+            seed_chain: None,
+            token_vault: None,
+        }
+        .to_account_metas(None),
+        data: crash3::instruction::CreateGame {
+            multiplier: 10_000,
+            game_name: format!("game {game_id}"),
+            seed_commitment: Some([1; 32]),
+            betting_slots: 1,
+            limits: BetLimits {
+                min_bet: 0,
+                max_bet: 0,
+                max_total_volume: 0,
+            },
+        }
+        .data(),
+    }
+}
+
+/// `remaining` is passed in scan order: chunks and non-archived GameStates.
+fn list_games_ix(offset: u64, newest_first: bool, remaining: &[Pubkey]) -> Instruction {
+    let mut accounts = crash3::accounts::ListGames {
+        game_index: pda(&[b"game_index"]),
+    }
+    .to_account_metas(None);
+    accounts.extend(remaining.iter().map(|key| AccountMeta::new_readonly(*key, false)));
+    Instruction {
+        program_id: crash3::ID,
+        accounts,
+        data: crash3::instruction::ListGames {
+            offset,
+            limit: crash3::MAX_LIST_GAMES as u8,
+            filter: GameListFilter::default(),
+            newest_first,
+        }
+        .data(),
+    }
+}
+
+async fn list_games(
+    banks: &mut BanksClient,
+    payer: &Keypair,
+    blockhash: Hash,
+    ix: Instruction,
+) -> Result<Vec<GameListItem>, TransactionError> {
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+    let sim = banks.simulate_transaction(tx).await.unwrap();
+    sim.result.unwrap()?;
+    let data = sim.simulation_details.unwrap().return_data.unwrap().data;
+    Ok(Vec::<GameListItem>::deserialize(&mut data.as_slice()).unwrap())
+}
+
+fn custom(code: crash3::ErrorCode) -> TransactionError {
+    TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(anchor_lang::error::ERROR_CODE_OFFSET + code as u32),
+    )
+}
+
+#[tokio::test]
+async fn list_games_across_chunks_and_archived_entries() {
+    let mut context = ProgramTest::new("crash3", crash3::ID, processor!(process))
+        .start_with_context()
+        .await;
+    let payer = context.payer.insecure_clone();
+    let blockhash = context.last_blockhash;
+    initialize(&mut context.banks_client, &payer, blockhash).await;
+
+    for game_id in 0..GAMES {
+        let ix = create_game_ix(&payer.pubkey(), game_id);
+        send(&mut context.banks_client, &payer, blockhash, &[ix], &[]).await.unwrap();
+    }
+
+    // ---- Void and archive one game in the second chunk ----
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context
+        .warp_to_slot(clock.slot + crash3::REVEAL_TIMEOUT_SLOTS + 10)
+        .unwrap();
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let ixs = [
+        Instruction {
+            program_id: crash3::ID,
+            accounts: crash3::accounts::VoidGame {
+                game_state: game_pda(ARCHIVED),
+                seed_chain: None,
+            }
+            .to_account_metas(None),
+            data: crash3::instruction::VoidGame {}.data(),
+        },
+        Instruction {
+            program_id: crash3::ID,
+            accounts: crash3::accounts::ArchiveGame {
+                game_state: game_pda(ARCHIVED),
+                chunk: chunk_pda(1),
+                config: pda(&[b"config"]),
+                signer: payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: crash3::instruction::ArchiveGame {}.data(),
+        },
+    ];
+    send(&mut context.banks_client, &payer, blockhash, &ixs, &[]).await.unwrap();
+    assert!(context
+        .banks_client
+        .get_account(game_pda(ARCHIVED))
+        .await
+        .unwrap()
+        .is_none());
+
+    let banks = &mut context.banks_client;
+
+    // ---- Oldest first, crossing from chunk 0 into chunk 1 ----
+    let mut remaining = vec![chunk_pda(0)];
+    remaining.extend((175..180).map(game_pda));
+    remaining.push(chunk_pda(1));
+    remaining.extend((180..185).filter(|id| *id != ARCHIVED).map(game_pda));
+    let items = list_games(banks, &payer, blockhash, list_games_ix(175, false, &remaining))
+        .await
+        .unwrap();
+    let ids: Vec<u64> = items.iter().map(|item| item.game_id).collect();
+    assert_eq!(ids, (175..185).collect::<Vec<_>>());
+    assert_eq!(items[0].game_name, "game 175");
+    assert_eq!(items[9].game_name, "game 184");
+
+    // ---- Newest first with an offset: skips 184 and 183, ends back in chunk 0 ----
+    // No GameState for the archived 181
+    let mut remaining = vec![chunk_pda(1), game_pda(182), game_pda(180), chunk_pda(0)];
+    remaining.extend((173..180).rev().map(game_pda));
+    let items = list_games(banks, &payer, blockhash, list_games_ix(2, true, &remaining))
+        .await
+        .unwrap();
+    let ids: Vec<u64> = items.iter().map(|item| item.game_id).collect();
+    assert_eq!(ids, (173..=182).rev().collect::<Vec<_>>());
+
+    // The archived game is listed from its chunk summary
+    let archived = &items[1];
+    assert_eq!(archived.game_id, ARCHIVED);
+    assert_eq!(archived.game_pda, game_pda(ARCHIVED));
+    assert!(archived.archived);
+    assert!(!archived.active);
+    assert!(!archived.crashed);
+    assert_eq!(archived.crash_point, 0);
+    assert_eq!(archived.game_name, "");
+    assert_eq!(items[0].game_name, "game 182");
+    assert!(!items[0].archived);
+
+    // ---- Missing GameState for the last entry ----
+    remaining.pop();
+    let result = list_games(banks, &payer, blockhash, list_games_ix(2, true, &remaining)).await;
+    let Err(err) = result else { panic!("listing without the last GameState succeeded") };
+    assert_eq!(err, custom(crash3::ErrorCode::MissingListAccount));
+
+    // ---- Chunk 1 passed where chunk 0 is due ----
+    let remaining = [chunk_pda(1), game_pda(0)];
+    let result = list_games(banks, &payer, blockhash, list_games_ix(0, false, &remaining)).await;
+    let Err(err) = result else { panic!("listing with the wrong chunk succeeded") };
+    assert_eq!(err, custom(crash3::ErrorCode::InvalidChunk));
+}
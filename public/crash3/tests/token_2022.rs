@@ -0,0 +1,238 @@
+// Runs the program natively under solana-program-test against the bundled
+// Token-2022 and associated-token programs.
+
+mod common;
+
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use common::{initialize, pda, process, send};
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::TransactionError,
+};
+use spl_token_2022::{
+    extension::{
+        transfer_fee::instruction::initialize_transfer_fee_config, ExtensionType,
+        StateWithExtensions,
+    },
+    state::{Account as TokenAccountState, Mint as MintState},
+};
+
+const DECIMALS: u8 = 6;
+const FEE_BPS: u16 = 100;
+
+/// Creates a Token-2022 mint with `extension` initialised by `init_extension`.
+async fn create_mint(
+    banks: &mut BanksClient,
+    payer: &Keypair,
+    blockhash: Hash,
+    extension: ExtensionType,
+    init_extension: Instruction,
+    mint: &Keypair,
+) {
+    let space = ExtensionType::try_calculate_account_len::<MintState>(&[extension]).unwrap();
+    let rent = banks.get_rent().await.unwrap().minimum_balance(space);
+    let ixs = [
+        system_instruction::create_account(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            rent,
+            space as u64,
+            &spl_token_2022::ID,
+        ),
+        init_extension,
+        spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            &payer.pubkey(),
+            None,
+            DECIMALS,
+        )
+        .unwrap(),
+    ];
+    send(banks, payer, blockhash, &ixs, &[mint]).await.unwrap();
+}
+
+fn init_token_vault_ix(payer: &Pubkey, mint: &Pubkey) -> Instruction {
+    let vault = pda(&[b"vault"]);
+    let treasury = pda(&[b"treasury"]);
+    Instruction {
+        program_id: crash3::ID,
+        accounts: crash3::accounts::InitTokenVault {
+            config: pda(&[b"config"]),
+            token_vault: pda(&[b"token_vault", mint.as_ref()]),
+            mint: *mint,
+            vault,
+            treasury,
+            vault_token_account: get_associated_token_address_with_program_id(
+                &vault,
+                mint,
+                &spl_token_2022::ID,
+            ),
+            treasury_token_account: get_associated_token_address_with_program_id(
+                &treasury,
+                mint,
+                &spl_token_2022::ID,
+            ),
+            signer: *payer,
+            token_program: spl_token_2022::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: crash3::instruction::InitTokenVault {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn deposit_credits_amount_received_after_transfer_fee() {
+    let (mut banks, payer, blockhash) =
+        ProgramTest::new("crash3", crash3::ID, processor!(process)).start().await;
+    initialize(&mut banks, &payer, blockhash).await;
+
+    let mint = Keypair::new();
+    let fee_config = initialize_transfer_fee_config(
+        &spl_token_2022::ID,
+        &mint.pubkey(),
+        None,
+        None,
+        FEE_BPS,
+        u64::MAX,
+    )
+    .unwrap();
+    create_mint(
+        &mut banks,
+        &payer,
+        blockhash,
+        ExtensionType::TransferFeeConfig,
+        fee_config,
+        &mint,
+    )
+    .await;
+
+    // ---- Player token account, funded ----
+    let user_tokens = Keypair::new();
+    let space = ExtensionType::try_calculate_account_len::<TokenAccountState>(&[
+        ExtensionType::TransferFeeAmount,
+    ])
+    .unwrap();
+    let rent = banks.get_rent().await.unwrap().minimum_balance(space);
+    let ixs = [
+        system_instruction::create_account(
+            &payer.pubkey(),
+            &user_tokens.pubkey(),
+            rent,
+            space as u64,
+            &spl_token_2022::ID,
+        ),
+        spl_token_2022::instruction::initialize_account3(
+            &spl_token_2022::ID,
+            &user_tokens.pubkey(),
+            &mint.pubkey(),
+            &payer.pubkey(),
+        )
+        .unwrap(),
+        spl_token_2022::instruction::mint_to(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            &user_tokens.pubkey(),
+            &payer.pubkey(),
+            &[],
+            1_000_000,
+        )
+        .unwrap(),
+    ];
+    send(&mut banks, &payer, blockhash, &ixs, &[&user_tokens]).await.unwrap();
+
+    // ---- Vault, player ledger, deposit ----
+    let user = pda(&[b"user_balance", payer.pubkey().as_ref(), mint.pubkey().as_ref()]);
+    let token_vault = pda(&[b"token_vault", mint.pubkey().as_ref()]);
+    let vault_token_account =
+        get_associated_token_address_with_program_id(&pda(&[b"vault"]), &mint.pubkey(), &spl_token_2022::ID);
+    let ixs = [
+        init_token_vault_ix(&payer.pubkey(), &mint.pubkey()),
+        Instruction {
+            program_id: crash3::ID,
+            accounts: crash3::accounts::CreateTokenUser {
+                user,
+                mint: mint.pubkey(),
+                user_wallet: payer.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crash3::instruction::CreateTokenUser {}.data(),
+        },
+        Instruction {
+            program_id: crash3::ID,
+            accounts: crash3::accounts::DepositToken {
+                user,
+                user_wallet: payer.pubkey(),
+                user_token_account: user_tokens.pubkey(),
+                config: pda(&[b"config"]),
+                token_vault,
+                vault_token_account,
+                mint: mint.pubkey(),
+                token_program: spl_token_2022::ID,
+            }
+            .to_account_metas(None),
+            data: crash3::instruction::DepositToken { amount: 10_000 }.data(),
+        },
+    ];
+    send(&mut banks, &payer, blockhash, &ixs, &[]).await.unwrap();
+
+    // 1% of 10_000 is withheld in the vault's token account
+    let vault_data = banks.get_account(vault_token_account).await.unwrap().unwrap().data;
+    let vault_state = StateWithExtensions::<TokenAccountState>::unpack(&vault_data).unwrap();
+    assert_eq!(vault_state.base.amount, 9_900);
+
+    let user_data = banks.get_account(user).await.unwrap().unwrap().data;
+    let user_state = crash3::UserBalance::try_deserialize(&mut user_data.as_slice()).unwrap();
+    assert_eq!(user_state.balance, 9_900);
+
+    let vault_book = banks.get_account(token_vault).await.unwrap().unwrap().data;
+    let vault_book = crash3::TokenVault::try_deserialize(&mut vault_book.as_slice()).unwrap();
+    assert_eq!(vault_book.total_user_balances, 9_900);
+    assert_eq!(vault_book.decimals, DECIMALS);
+}
+
+#[tokio::test]
+async fn init_token_vault_rejects_permanent_delegate_mint() {
+    let (mut banks, payer, blockhash) =
+        ProgramTest::new("crash3", crash3::ID, processor!(process)).start().await;
+    initialize(&mut banks, &payer, blockhash).await;
+
+    let mint = Keypair::new();
+    let delegate = spl_token_2022::instruction::initialize_permanent_delegate(
+        &spl_token_2022::ID,
+        &mint.pubkey(),
+        &payer.pubkey(),
+    )
+    .unwrap();
+    create_mint(
+        &mut banks,
+        &payer,
+        blockhash,
+        ExtensionType::PermanentDelegate,
+        delegate,
+        &mint,
+    )
+    .await;
+
+    let ix = init_token_vault_ix(&payer.pubkey(), &mint.pubkey());
+    let err = send(&mut banks, &payer, blockhash, &[ix], &[])
+        .await
+        .unwrap_err()
+        .unwrap();
+    let code = anchor_lang::error::ERROR_CODE_OFFSET + crash3::ErrorCode::UnsupportedMint as u32;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(code))
+    );
+    let token_vault = pda(&[b"token_vault", mint.pubkey().as_ref()]);
+    assert!(banks.get_account(token_vault).await.unwrap().is_none());
+}
@@ -67,6 +67,7 @@ pub mod crash_game {
         ctx: Context<CreateGame>,
         multiplier: u64,
         game_name: String,
+        game_seed: u64,
     ) -> Result<()> {
         require!(multiplier >= 100 && multiplier <= 10000, ErrorCode::InvalidMultiplier);
         require!(!game_name.is_empty(), ErrorCode::InvalidGameName);
@@ -87,6 +88,7 @@ pub mod crash_game {
         game.admin = ctx.accounts.signer.key();
         game.crashed = false;
         game.game_id = game_key; // Use the key we got earlier
+        game.game_seed = game_seed;
 
         Ok(())
     }
@@ -226,6 +228,7 @@ pub struct GameState {
     pub admin: Pubkey,
     pub crashed: bool,
     pub game_id: Pubkey, // PDA of this game
+    pub game_seed: u64,  // PDA seed, so the address can be re-derived
 }
 
 #[account]
@@ -293,15 +296,16 @@ pub struct Withdraw<'info> {
     pub system_program: Program<'info, System>,
 }
 
-// UNIQUE PDA PER GAME — USING u32 TIMESTAMP
+// UNIQUE PDA PER GAME — CALLER-CHOSEN u64 SEED
+// (a u32 timestamp collided for two games in the same second and wraps in 2106)
 #[derive(Accounts)]
-#[instruction(multiplier: u64, game_name: String)]
+#[instruction(multiplier: u64, game_name: String, game_seed: u64)]
 pub struct CreateGame<'info> {
     #[account(
         init,
         payer = signer,
-        space = 8 + 8 + 1 + 8 + 8 + 8 + 8 + 4 + game_name.len() + 32 + 1 + 32,
-        seeds = [b"game", &(Clock::get()?.unix_timestamp as u32).to_le_bytes()],
+        space = 8 + 8 + 1 + 8 + 8 + 8 + 8 + 4 + game_name.len() + 32 + 1 + 32 + 8,
+        seeds = [b"game".as_ref(), &game_seed.to_le_bytes()],
         bump
     )]
     pub game_state: Account<'info, GameState>,
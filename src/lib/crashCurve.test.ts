@@ -0,0 +1,48 @@
+// src/lib/crashCurve.test.ts
+// Same vectors as the `tests` module in public/crash3/lib.rs, so the client curve
+// can't drift from the program. Run with `npm run test:curve`.
+
+import { test } from 'node:test';
+import assert from 'node:assert/strict';
+import { PublicKey } from '@solana/web3.js';
+import {
+  MAX_MULTIPLIER,
+  MIN_MULTIPLIER,
+  cashoutValid,
+  deriveCrashPoint,
+  liveMultiplier,
+} from './crashCurve';
+
+const fill = (byte: number) => new Uint8Array(32).fill(byte);
+
+test('liveMultiplier vectors', () => {
+  assert.equal(liveMultiplier(0), 100);
+  assert.equal(liveMultiplier(1), 102);
+  assert.equal(liveMultiplier(29), 198);
+  assert.equal(liveMultiplier(194), 9_958);
+  assert.equal(liveMultiplier(195), MAX_MULTIPLIER);
+  assert.equal(liveMultiplier(500), MAX_MULTIPLIER);
+  assert.equal(liveMultiplier(BigInt('18446744073709551615')), MAX_MULTIPLIER);
+});
+
+test('deriveCrashPoint vectors', async () => {
+  const game = new PublicKey(fill(3));
+  const entropy = fill(9);
+  assert.equal(await deriveCrashPoint(fill(7), game, entropy, 0), 119);
+  assert.equal(await deriveCrashPoint(fill(7), game, entropy, 100), 118);
+  assert.equal(await deriveCrashPoint(fill(7), game, entropy, 500), 113);
+
+  // The edge pushes 1.01x below 1.00x, which floors
+  assert.equal(await deriveCrashPoint(fill(27), game, entropy, 0), 101);
+  assert.equal(await deriveCrashPoint(fill(27), game, entropy, 100), MIN_MULTIPLIER);
+  // Far tail clamps to the cap
+  assert.equal(await deriveCrashPoint(fill(98), game, entropy, 100), MAX_MULTIPLIER);
+});
+
+test('cashoutValid vectors', () => {
+  assert.equal(cashoutValid(249, 250, true), true);
+  assert.equal(cashoutValid(250, 250, true), false);
+  assert.equal(cashoutValid(300, 250, true), false);
+  // Ran to the table cap without crashing
+  assert.equal(cashoutValid(MAX_MULTIPLIER, MAX_MULTIPLIER, false), true);
+});
@@ -0,0 +1,83 @@
+// src/lib/crashCurve.ts
+// Client mirror of the on-chain curve in public/crash3/lib.rs (`live_multiplier`,
+// `game_multiplier_at`, `derive_crash_point`). All math is integer BigInt so the
+// results match the program bit-for-bit; multipliers are in hundredths (250 = 2.50x).
+
+import { PublicKey } from '@solana/web3.js';
+
+export const MIN_MULTIPLIER = 100;
+export const MAX_MULTIPLIER = 10_000;
+export const MULTIPLIER_GROWTH_BPS_PER_SLOT = 240;
+export const REVEAL_TIMEOUT_SLOTS = 450;
+
+const ONE = BigInt(1_000_000_000_000);
+const ZERO = BigInt(0);
+const HUNDRED = BigInt(100);
+const BPS = BigInt(10_000);
+
+// Live multiplier after `elapsedSlots` of running time, capped at MAX_MULTIPLIER.
+export function liveMultiplier(elapsedSlots: number | bigint): number {
+  const cap = (ONE * BigInt(MAX_MULTIPLIER)) / HUNDRED;
+
+  let base = ONE + (ONE * BigInt(MULTIPLIER_GROWTH_BPS_PER_SLOT)) / BPS;
+  let acc = ONE;
+  let n = BigInt(elapsedSlots);
+  while (n > ZERO) {
+    if (n % BigInt(2) === BigInt(1)) {
+      acc = (acc * base) / ONE;
+      if (acc >= cap) {
+        return MAX_MULTIPLIER;
+      }
+    }
+    base = (base * base) / ONE;
+    if (base > cap) base = cap;
+    n /= BigInt(2);
+  }
+  return Number((acc * HUNDRED) / ONE);
+}
+
+// Multiplier of a round at `slot`: 1.00x until `startSlot`, capped at the table multiplier.
+export function gameMultiplierAt(
+  startSlot: number | bigint,
+  tableMultiplier: number,
+  slot: number | bigint
+): number {
+  const elapsed = BigInt(slot) - BigInt(startSlot);
+  return Math.min(liveMultiplier(elapsed > ZERO ? elapsed : ZERO), tableMultiplier);
+}
+
+// Crash point from the revealed server seed, the game PDA and the stored entropy hash
+// (GameState.entropyHash). Lets players verify a round from on-chain data alone.
+export async function deriveCrashPoint(
+  serverSeed: Uint8Array,
+  game: PublicKey,
+  entropyHash: Uint8Array,
+  houseEdgeBps: number
+): Promise<number> {
+  const input = new Uint8Array(96);
+  input.set(serverSeed, 0);
+  input.set(game.toBytes(), 32);
+  input.set(entropyHash, 64);
+  const digest = new Uint8Array(await crypto.subtle.digest('SHA-256', input));
+
+  // First 8 bytes little-endian, top 52 bits
+  let word = ZERO;
+  for (let i = 7; i >= 0; i--) {
+    word = (word << BigInt(8)) | BigInt(digest[i]);
+  }
+  const h = word >> BigInt(12);
+  const e = BigInt(2) ** BigInt(52);
+  const point = (HUNDRED * e * (BPS - BigInt(houseEdgeBps))) / BPS / (e - h);
+
+  return Number(point < BigInt(MIN_MULTIPLIER) ? BigInt(MIN_MULTIPLIER)
+    : point > BigInt(MAX_MULTIPLIER) ? BigInt(MAX_MULTIPLIER) : point);
+}
+
+// A cash-out stands only strictly below the crash point, or if the round hit the table cap.
+export function cashoutValid(
+  cashoutMultiplier: number,
+  crashPoint: number,
+  crashed: boolean
+): boolean {
+  return cashoutMultiplier < crashPoint || !crashed;
+}